@@ -0,0 +1,18 @@
+/// 64-bit FNV-1a hash of the input, stable across platforms and releases.
+pub fn checksum(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_fnv1a_values() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, checksum(""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, checksum("a"));
+        assert_eq!(0x8594_4171_f739_67e8, checksum("foobar"));
+    }
+}
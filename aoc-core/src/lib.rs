@@ -0,0 +1,84 @@
+mod checksum;
+mod parse;
+mod rng;
+
+use std::fmt::Display;
+
+pub use checksum::checksum;
+pub use parse::{parse_number, ParseError, Position};
+pub use rng::Rng;
+
+/// A single day of the calendar: how to read the puzzle input and how to answer both parts.
+pub trait Solution {
+    const DAY: u8;
+    /// The puzzle input shipped with the day's crate.
+    const INPUT: &'static str;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Object safe view of a [`Solution`], so days with different input types fit in one registry.
+pub trait Puzzle {
+    fn day(&self) -> u8;
+    fn input(&self) -> &'static str;
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError>;
+}
+
+/// Parsed input of a [`Puzzle`] ready to be solved.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> String;
+}
+
+struct Prepared<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for Prepared<S> {
+    fn solve(&self, part: Part) -> String {
+        match part {
+            Part::One => S::part1(&self.0).to_string(),
+            Part::Two => S::part2(&self.0).to_string(),
+        }
+    }
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn input(&self) -> &'static str {
+        S::INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        Ok(Box::new(Prepared::<S>(S::parse(input)?)))
+    }
+}
@@ -0,0 +1,214 @@
+use std::{fmt, str::FromStr};
+
+/// 1-based line and column of a token in the parsed input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub const START: Position = Position { line: 1, column: 1 };
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidNumber {
+        position: Position,
+        token: String,
+    },
+    UnexpectedToken {
+        position: Position,
+        token: String,
+        expected: String,
+    },
+    MissingToken {
+        position: Position,
+        expected: String,
+    },
+    /// `token` parsed fine but pushed an accumulated value past what its type can hold.
+    Overflow {
+        position: Position,
+        token: String,
+        reason: String,
+    },
+}
+
+impl ParseError {
+    pub fn unexpected(token: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::UnexpectedToken {
+            position: Position::START,
+            token: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// A token is missing at the end of `line`.
+    pub fn missing(line: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::MissingToken {
+            position: Position {
+                line: 1,
+                column: line.chars().count() + 1,
+            },
+            expected: expected.into(),
+        }
+    }
+
+    pub fn overflow(token: &str, reason: impl Into<String>) -> ParseError {
+        ParseError::Overflow {
+            position: Position::START,
+            token: token.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        match self {
+            ParseError::InvalidNumber { position, .. }
+            | ParseError::UnexpectedToken { position, .. }
+            | ParseError::MissingToken { position, .. }
+            | ParseError::Overflow { position, .. } => *position,
+        }
+    }
+
+    pub fn token(&self) -> &str {
+        match self {
+            ParseError::InvalidNumber { token, .. }
+            | ParseError::UnexpectedToken { token, .. }
+            | ParseError::Overflow { token, .. } => token,
+            ParseError::MissingToken { .. } => "",
+        }
+    }
+
+    fn position_mut(&mut self) -> &mut Position {
+        match self {
+            ParseError::InvalidNumber { position, .. }
+            | ParseError::UnexpectedToken { position, .. }
+            | ParseError::MissingToken { position, .. }
+            | ParseError::Overflow { position, .. } => position,
+        }
+    }
+
+    /// Moves an error reported by a single line parser onto `line` of the whole input.
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.position_mut().line += line - 1;
+        self
+    }
+
+    /// Shifts an error reported for `inner` so the column points into `outer`,
+    /// where `inner` is a subslice of `outer`.
+    pub fn within(mut self, outer: &str, inner: &str) -> ParseError {
+        let offset = (inner.as_ptr() as usize)
+            .checked_sub(outer.as_ptr() as usize)
+            .filter(|offset| *offset <= outer.len())
+            .expect("inner is a subslice of outer");
+        self.position_mut().column += outer[..offset].chars().count();
+        self
+    }
+
+    /// Formats the error like a compiler diagnostic, quoting the offending line of `source`.
+    pub fn render(&self, origin: &str, source: &str) -> String {
+        let Position { line, column } = self.position();
+        let quoted = source.lines().nth(line - 1).unwrap_or_default();
+        let gutter = " ".repeat(line.to_string().len());
+        let marker = "^".repeat(self.token().chars().count().max(1));
+
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self.message(),
+            gutter,
+            origin,
+            line,
+            column,
+            gutter,
+            line,
+            quoted,
+            gutter,
+            " ".repeat(column - 1),
+            marker
+        )
+    }
+
+    fn message(&self) -> String {
+        match self {
+            ParseError::InvalidNumber { token, .. } => format!("invalid number `{}`", token),
+            ParseError::UnexpectedToken {
+                token, expected, ..
+            } => format!("unexpected `{}`, expected {}", token, expected),
+            ParseError::MissingToken { expected, .. } => format!("missing {}", expected),
+            ParseError::Overflow { token, reason, .. } => {
+                format!("adding `{}` overflows: {}", token, reason)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Position { line, column } = self.position();
+        write!(f, "{}:{}: {}", line, column, self.message())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse_number<T: FromStr>(token: &str) -> Result<T, ParseError> {
+    token.parse::<T>().map_err(|_| ParseError::InvalidNumber {
+        position: Position::START,
+        token: token.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_errors_carry_the_token() {
+        assert_eq!(Ok(42), parse_number::<u32>("42"));
+        assert_eq!(
+            Err(ParseError::InvalidNumber {
+                position: Position::START,
+                token: "4x".to_string()
+            }),
+            parse_number::<u32>("4x")
+        );
+    }
+
+    #[test]
+    fn relocate_error_into_the_input() {
+        let line = "2-4,6-x";
+        let token = &line[6..];
+        let err = parse_number::<u32>(token)
+            .unwrap_err()
+            .within(line, token)
+            .on_line(3);
+
+        assert_eq!(Position { line: 3, column: 7 }, err.position());
+        assert_eq!("x", err.token());
+        assert_eq!("3:7: invalid number `x`", err.to_string());
+    }
+
+    #[test]
+    fn missing_token_points_past_the_line() {
+        let err = ParseError::missing("A", "a second column").on_line(2);
+        assert_eq!(Position { line: 2, column: 2 }, err.position());
+    }
+
+    #[test]
+    fn render_quotes_the_line() {
+        let source = "A Y\nB Q\n";
+        let err = ParseError::unexpected("Q", "one of X, Y, Z")
+            .within("B Q", &"B Q"[2..])
+            .on_line(2);
+
+        assert_eq!(
+            "error: unexpected `Q`, expected one of X, Y, Z
+ --> input.txt:2:3
+  |
+2 | B Q
+  |   ^",
+            err.render("input.txt", source)
+        );
+    }
+}
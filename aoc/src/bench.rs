@@ -0,0 +1,217 @@
+use std::{fmt, hint::black_box, time::Duration, time::Instant};
+
+use aoc_core::{ParseError, Part, Puzzle};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    fn from_name(name: &str) -> Option<Phase> {
+        match name {
+            "parse" => Some(Phase::Parse),
+            "part1" => Some(Phase::Part1),
+            "part2" => Some(Phase::Part2),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part1"),
+            Phase::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p99: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "at least one sample");
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: percentile(samples, 50),
+            p99: percentile(samples, 99),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Times parsing and both parts of `puzzle` separately, `iterations` times each.
+pub fn measure(
+    puzzle: &dyn Puzzle,
+    input: &str,
+    iterations: usize,
+) -> Result<Vec<Measurement>, ParseError> {
+    let mut parse_samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let started = Instant::now();
+        black_box(puzzle.parse(black_box(input))?);
+        parse_samples.push(started.elapsed());
+    }
+
+    let parsed = puzzle.parse(input)?;
+    let mut measurements = vec![Measurement {
+        day: puzzle.day(),
+        phase: Phase::Parse,
+        stats: Stats::from_samples(&mut parse_samples),
+    }];
+
+    for (phase, part) in [(Phase::Part1, Part::One), (Phase::Part2, Part::Two)] {
+        let mut samples: Vec<Duration> = (0..iterations)
+            .map(|_| {
+                let started = Instant::now();
+                black_box(parsed.solve(part));
+                started.elapsed()
+            })
+            .collect();
+        measurements.push(Measurement {
+            day: puzzle.day(),
+            phase,
+            stats: Stats::from_samples(&mut samples),
+        });
+    }
+
+    Ok(measurements)
+}
+
+/// Serialises measurements as `<day> <phase> <min ns> <median ns> <p99 ns>` lines.
+pub fn to_baseline(measurements: &[Measurement]) -> String {
+    measurements
+        .iter()
+        .map(|m| {
+            format!(
+                "{} {} {} {} {}\n",
+                m.day,
+                m.phase,
+                m.stats.min.as_nanos(),
+                m.stats.median.as_nanos(),
+                m.stats.p99.as_nanos()
+            )
+        })
+        .collect()
+}
+
+pub fn parse_baseline(baseline: &str) -> Result<Vec<Measurement>, String> {
+    baseline
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let invalid = || format!("line {}: invalid baseline entry `{}`", index + 1, line);
+            let nanos = |token: &str| token.parse::<u64>().map(Duration::from_nanos);
+
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [day, phase, min, median, p99] => Ok(Measurement {
+                    day: day.parse().map_err(|_| invalid())?,
+                    phase: Phase::from_name(phase).ok_or_else(invalid)?,
+                    stats: Stats {
+                        min: nanos(min).map_err(|_| invalid())?,
+                        median: nanos(median).map_err(|_| invalid())?,
+                        p99: nanos(p99).map_err(|_| invalid())?,
+                    },
+                }),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+/// Relative change of the median against the baseline, e.g. `0.25` for 25% slower.
+pub fn change(current: &Measurement, baseline: &[Measurement]) -> Option<f64> {
+    baseline
+        .iter()
+        .find(|b| b.day == current.day && b.phase == current.phase)
+        .filter(|b| !b.stats.median.is_zero())
+        .map(|b| current.stats.median.as_secs_f64() / b.stats.median.as_secs_f64() - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use day4::Day4;
+
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_micros(*v)).collect()
+    }
+
+    #[test]
+    fn stats_use_nearest_rank() {
+        let mut samples = micros(&(1..=100).rev().collect::<Vec<_>>());
+        assert_eq!(
+            Stats {
+                min: Duration::from_micros(1),
+                median: Duration::from_micros(50),
+                p99: Duration::from_micros(99),
+            },
+            Stats::from_samples(&mut samples)
+        );
+
+        let mut single = micros(&[7]);
+        let stats = Stats::from_samples(&mut single);
+        assert_eq!(stats.min, stats.p99);
+    }
+
+    #[test]
+    fn measure_every_phase() {
+        let measurements = measure(&Day4, "2-4,6-8\n2-8,3-7\n", 3).unwrap();
+        assert_eq!(
+            vec![Phase::Parse, Phase::Part1, Phase::Part2],
+            measurements.iter().map(|m| m.phase).collect::<Vec<_>>()
+        );
+        assert!(measure(&Day4, "2-4\n", 3).is_err());
+    }
+
+    #[test]
+    fn baseline_round_trip_and_change() {
+        let measurement = Measurement {
+            day: 3,
+            phase: Phase::Part2,
+            stats: Stats {
+                min: Duration::from_nanos(100),
+                median: Duration::from_nanos(200),
+                p99: Duration::from_nanos(300),
+            },
+        };
+        let baseline = to_baseline(&[measurement]);
+        assert_eq!("3 part2 100 200 300\n", baseline);
+        assert_eq!(Ok(vec![measurement]), parse_baseline(&baseline));
+        assert!(parse_baseline("3 part3 1 2 3").is_err());
+
+        let slower = Measurement {
+            stats: Stats {
+                median: Duration::from_nanos(300),
+                ..measurement.stats
+            },
+            ..measurement
+        };
+        assert_eq!(Some(0.5), change(&slower, &[measurement]));
+        assert_eq!(None, change(&slower, &[]));
+    }
+}
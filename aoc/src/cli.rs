@@ -0,0 +1,356 @@
+use std::{fmt, path::PathBuf, str::FromStr};
+
+use aoc_core::Part;
+
+use crate::report::Format;
+
+pub const USAGE: &str = "usage:
+    aoc run [--day <1-25>] [--part <1|2>] [--input <path|->] [--format <text|json|csv>]
+    aoc bench [--day <1-25>] [--input <path|->] [--iterations <n>]
+              [--baseline <path>] [--save <path>] [--threshold <percent>]
+    aoc generate --day <1-25> [--size <n>] [--seed <n>]
+    aoc list";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    Generate(GenerateArgs),
+    List,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RunArgs {
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub input: Option<InputSource>,
+    pub format: Format,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    pub day: Option<u8>,
+    pub input: Option<InputSource>,
+    pub iterations: usize,
+    pub baseline: Option<PathBuf>,
+    pub save: Option<PathBuf>,
+    /// Allowed slowdown of the median against the baseline, in percent.
+    pub threshold: f64,
+}
+
+impl Default for BenchArgs {
+    fn default() -> Self {
+        BenchArgs {
+            day: None,
+            input: None,
+            iterations: 100,
+            baseline: None,
+            save: None,
+            threshold: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateArgs {
+    pub day: u8,
+    /// Number of records: elves, rounds, rucksacks, pairs, moves or datastream length.
+    pub size: usize,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    Path(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownFlag(String),
+    MissingValue(&'static str),
+    MissingFlag(&'static str),
+    InvalidValue(&'static str, String),
+    InputWithoutDay,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::MissingCommand => write!(f, "missing command"),
+            CliError::UnknownCommand(command) => write!(f, "unknown command `{}`", command),
+            CliError::UnknownFlag(flag) => write!(f, "unknown flag `{}`", flag),
+            CliError::MissingValue(flag) => write!(f, "`{}` expects a value", flag),
+            CliError::MissingFlag(flag) => write!(f, "`{}` is required", flag),
+            CliError::InvalidValue(flag, value) => {
+                write!(f, "invalid value `{}` for `{}`", value, flag)
+            }
+            CliError::InputWithoutDay => write!(f, "`--input` requires `--day`"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+pub fn parse_args<I>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("generate") => parse_generate(args).map(Command::Generate),
+        Some("list") => Ok(Command::List),
+        Some(command) => Err(CliError::UnknownCommand(command.to_string())),
+        None => Err(CliError::MissingCommand),
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<RunArgs, CliError> {
+    let mut run_args = RunArgs::default();
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--day" => run_args.day = Some(parse_day(&next_value(&mut args, "--day")?)?),
+            "--part" => run_args.part = Some(parse_part(&next_value(&mut args, "--part")?)?),
+            "--input" => run_args.input = Some(parse_input(next_value(&mut args, "--input")?)),
+            "--format" => run_args.format = parse_format(&next_value(&mut args, "--format")?)?,
+            _ => return Err(CliError::UnknownFlag(flag)),
+        }
+    }
+
+    if run_args.input.is_some() && run_args.day.is_none() {
+        return Err(CliError::InputWithoutDay);
+    }
+
+    Ok(run_args)
+}
+
+fn parse_generate(mut args: impl Iterator<Item = String>) -> Result<GenerateArgs, CliError> {
+    let mut day = None;
+    let mut size = 1000;
+    let mut seed = 0;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--day" => day = Some(parse_day(&next_value(&mut args, "--day")?)?),
+            "--size" => size = parse_number("--size", next_value(&mut args, "--size")?)?,
+            "--seed" => seed = parse_number("--seed", next_value(&mut args, "--seed")?)?,
+            _ => return Err(CliError::UnknownFlag(flag)),
+        }
+    }
+
+    Ok(GenerateArgs {
+        day: day.ok_or(CliError::MissingFlag("--day"))?,
+        size,
+        seed,
+    })
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<BenchArgs, CliError> {
+    let mut bench_args = BenchArgs::default();
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--day" => bench_args.day = Some(parse_day(&next_value(&mut args, "--day")?)?),
+            "--input" => bench_args.input = Some(parse_input(next_value(&mut args, "--input")?)),
+            "--iterations" => {
+                let value = next_value(&mut args, "--iterations")?;
+                bench_args.iterations = match parse_number("--iterations", value.clone())? {
+                    0 => return Err(CliError::InvalidValue("--iterations", value)),
+                    iterations => iterations,
+                };
+            }
+            "--baseline" => {
+                bench_args.baseline = Some(PathBuf::from(next_value(&mut args, "--baseline")?))
+            }
+            "--save" => bench_args.save = Some(PathBuf::from(next_value(&mut args, "--save")?)),
+            "--threshold" => {
+                bench_args.threshold =
+                    parse_number("--threshold", next_value(&mut args, "--threshold")?)?
+            }
+            _ => return Err(CliError::UnknownFlag(flag)),
+        }
+    }
+
+    if bench_args.input.is_some() && bench_args.day.is_none() {
+        return Err(CliError::InputWithoutDay);
+    }
+
+    Ok(bench_args)
+}
+
+fn next_value(
+    args: &mut impl Iterator<Item = String>,
+    flag: &'static str,
+) -> Result<String, CliError> {
+    args.next().ok_or(CliError::MissingValue(flag))
+}
+
+fn parse_number<T: FromStr>(flag: &'static str, value: String) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::InvalidValue(flag, value))
+}
+
+fn parse_input(value: String) -> InputSource {
+    match value.as_str() {
+        "-" => InputSource::Stdin,
+        _ => InputSource::Path(PathBuf::from(value)),
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8, CliError> {
+    match value.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(CliError::InvalidValue("--day", value.to_string())),
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, CliError> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(CliError::InvalidValue("--part", value.to_string())),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, CliError> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        _ => Err(CliError::InvalidValue("--format", value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn run_everything_by_default() {
+        assert_eq!(
+            Ok(Command::Run(RunArgs::default())),
+            parse_args(args("run"))
+        );
+    }
+
+    #[test]
+    fn run_single_part_from_file() {
+        assert_eq!(
+            Ok(Command::Run(RunArgs {
+                day: Some(4),
+                part: Some(Part::Two),
+                input: Some(InputSource::Path(PathBuf::from("day4/input.txt"))),
+                format: Format::Text,
+            })),
+            parse_args(args("run --day 4 --part 2 --input day4/input.txt"))
+        );
+    }
+
+    #[test]
+    fn dash_reads_from_stdin() {
+        assert_eq!(
+            Ok(Command::Run(RunArgs {
+                day: Some(1),
+                part: None,
+                input: Some(InputSource::Stdin),
+                format: Format::Text,
+            })),
+            parse_args(args("run --day 1 --input -"))
+        );
+    }
+
+    #[test]
+    fn select_output_format() {
+        assert_eq!(
+            Ok(Command::Run(RunArgs {
+                format: Format::Csv,
+                ..RunArgs::default()
+            })),
+            parse_args(args("run --format csv"))
+        );
+        assert_eq!(
+            Err(CliError::InvalidValue("--format", "xml".to_string())),
+            parse_args(args("run --format xml"))
+        );
+    }
+
+    #[test]
+    fn bench_against_baseline() {
+        assert_eq!(
+            Ok(Command::Bench(BenchArgs {
+                day: Some(2),
+                iterations: 50,
+                baseline: Some(PathBuf::from("bench.txt")),
+                threshold: 5.0,
+                ..BenchArgs::default()
+            })),
+            parse_args(args(
+                "bench --day 2 --iterations 50 --baseline bench.txt --threshold 5"
+            ))
+        );
+        assert_eq!(
+            Err(CliError::InvalidValue("--iterations", "0".to_string())),
+            parse_args(args("bench --iterations 0"))
+        );
+    }
+
+    #[test]
+    fn generate_with_defaults() {
+        assert_eq!(
+            Ok(Command::Generate(GenerateArgs {
+                day: 3,
+                size: 1000,
+                seed: 0,
+            })),
+            parse_args(args("generate --day 3"))
+        );
+        assert_eq!(
+            Ok(Command::Generate(GenerateArgs {
+                day: 5,
+                size: 20,
+                seed: 42,
+            })),
+            parse_args(args("generate --seed 42 --size 20 --day 5"))
+        );
+        assert_eq!(
+            Err(CliError::MissingFlag("--day")),
+            parse_args(args("generate --size 10"))
+        );
+    }
+
+    #[test]
+    fn reject_invalid_arguments() {
+        assert_eq!(Err(CliError::MissingCommand), parse_args(args("")));
+        assert_eq!(
+            Err(CliError::UnknownCommand("walk".to_string())),
+            parse_args(args("walk"))
+        );
+        assert_eq!(
+            Err(CliError::InvalidValue("--day", "26".to_string())),
+            parse_args(args("run --day 26"))
+        );
+        assert_eq!(
+            Err(CliError::InvalidValue("--part", "3".to_string())),
+            parse_args(args("run --part 3"))
+        );
+        assert_eq!(
+            Err(CliError::MissingValue("--day")),
+            parse_args(args("run --day"))
+        );
+        assert_eq!(
+            Err(CliError::InputWithoutDay),
+            parse_args(args("run --input -"))
+        );
+    }
+}
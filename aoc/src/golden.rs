@@ -0,0 +1,174 @@
+use std::fmt;
+
+use aoc_core::{checksum, Part, Puzzle};
+
+/// Expected answers for one input, as listed in a day's `answers.txt`.
+#[derive(Debug, PartialEq)]
+pub struct Expected {
+    pub checksum: u64,
+    pub part1: String,
+    pub part2: String,
+}
+
+impl Expected {
+    fn answer(&self, part: Part) -> &str {
+        match part {
+            Part::One => &self.part1,
+            Part::Two => &self.part2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Drift {
+    UnknownInput {
+        day: u8,
+        checksum: u64,
+    },
+    Unparsable {
+        day: u8,
+        error: String,
+    },
+    Answer {
+        day: u8,
+        part: Part,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::UnknownInput { day, checksum } => {
+                write!(f, "day {}: no answers for input {:016x}", day, checksum)
+            }
+            Drift::Unparsable { day, error } => write!(f, "day {}: {}", day, error),
+            Drift::Answer {
+                day,
+                part,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "day {} part {}: expected {}, got {}",
+                day, part, expected, actual
+            ),
+        }
+    }
+}
+
+/// Reads a manifest of `<checksum> <part 1> <part 2>` lines; `#` starts a comment line.
+pub fn parse_manifest(manifest: &str) -> Result<Vec<Expected>, String> {
+    manifest
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(
+            |(index, line)| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [hash, part1, part2] => Ok(Expected {
+                    checksum: u64::from_str_radix(hash, 16)
+                        .map_err(|_| format!("line {}: invalid checksum `{}`", index + 1, hash))?,
+                    part1: part1.to_string(),
+                    part2: part2.to_string(),
+                }),
+                _ => Err(format!(
+                    "line {}: expected `<checksum> <part 1> <part 2>`",
+                    index + 1
+                )),
+            },
+        )
+        .collect()
+}
+
+/// Solves `input` and compares both answers with the manifest entry for its checksum.
+pub fn verify(puzzle: &dyn Puzzle, input: &str, manifest: &[Expected]) -> Vec<Drift> {
+    let day = puzzle.day();
+    let input_checksum = checksum(input);
+
+    let Some(expected) = manifest.iter().find(|e| e.checksum == input_checksum) else {
+        return vec![Drift::UnknownInput {
+            day,
+            checksum: input_checksum,
+        }];
+    };
+    let parsed = match puzzle.parse(input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return vec![Drift::Unparsable {
+                day,
+                error: err.to_string(),
+            }]
+        }
+    };
+
+    Part::ALL
+        .iter()
+        .map(|part| (*part, parsed.solve(*part)))
+        .filter(|(part, actual)| actual != expected.answer(*part))
+        .map(|(part, actual)| Drift::Answer {
+            day,
+            part,
+            expected: expected.answer(part).to_string(),
+            actual,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use day4::Day4;
+
+    use super::*;
+
+    const SAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+
+    #[test]
+    fn parse_manifest_skips_comments() {
+        assert_eq!(
+            Ok(vec![Expected {
+                checksum: 0xff,
+                part1: "CMZ".to_string(),
+                part2: "MCD".to_string(),
+            }]),
+            parse_manifest("# checksum part1 part2\n\n00000000000000ff CMZ MCD\n")
+        );
+        assert!(parse_manifest("ff CMZ").is_err());
+        assert!(parse_manifest("zz 1 2").is_err());
+    }
+
+    #[test]
+    fn verify_matching_answers() {
+        let manifest = [Expected {
+            checksum: checksum(SAMPLE),
+            part1: "2".to_string(),
+            part2: "4".to_string(),
+        }];
+        assert!(verify(&Day4, SAMPLE, &manifest).is_empty());
+    }
+
+    #[test]
+    fn verify_reports_drift() {
+        let manifest = [Expected {
+            checksum: checksum(SAMPLE),
+            part1: "2".to_string(),
+            part2: "5".to_string(),
+        }];
+        assert_eq!(
+            vec![Drift::Answer {
+                day: 4,
+                part: Part::Two,
+                expected: "5".to_string(),
+                actual: "4".to_string(),
+            }],
+            verify(&Day4, SAMPLE, &manifest)
+        );
+        assert_eq!(
+            vec![Drift::UnknownInput {
+                day: 4,
+                checksum: checksum(SAMPLE),
+            }],
+            verify(&Day4, SAMPLE, &[])
+        );
+    }
+}
@@ -0,0 +1,35 @@
+pub mod bench;
+pub mod cli;
+pub mod golden;
+pub mod report;
+
+use aoc_core::{Puzzle, Rng};
+use day1::Day1;
+use day2::Day2;
+use day3::Day3;
+use day4::Day4;
+use day5::Day5;
+use day6::Day6;
+
+pub fn puzzles() -> [&'static dyn Puzzle; 6] {
+    [&Day1, &Day2, &Day3, &Day4, &Day5, &Day6]
+}
+
+pub fn puzzle(day: u8) -> Option<&'static dyn Puzzle> {
+    puzzles().into_iter().find(|puzzle| puzzle.day() == day)
+}
+
+/// Builds a synthetic puzzle input of the given size.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+pub fn generator(day: u8) -> Option<Generator> {
+    match day {
+        1 => Some(day1::generate::generate),
+        2 => Some(day2::generate::generate),
+        3 => Some(day3::generate::generate),
+        4 => Some(day4::generate::generate),
+        5 => Some(day5::generate::generate),
+        6 => Some(day6::generate::generate),
+        _ => None,
+    }
+}
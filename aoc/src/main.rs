@@ -0,0 +1,185 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+    time::Instant,
+};
+
+use aoc::{
+    bench::{self, Measurement},
+    cli::{self, BenchArgs, Command, GenerateArgs, InputSource, RunArgs},
+    report::Answer,
+};
+use aoc_core::{checksum, Part, Puzzle, Rng};
+
+fn main() -> ExitCode {
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match command {
+        Command::Run(run_args) => run(run_args),
+        Command::Bench(bench_args) => run_bench(bench_args),
+        Command::Generate(generate_args) => generate(generate_args),
+        Command::List => {
+            aoc::puzzles()
+                .iter()
+                .for_each(|puzzle| println!("day {}", puzzle.day()));
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(run_args: RunArgs) -> Result<(), String> {
+    let puzzles = select_puzzles(run_args.day)?;
+    let parts = match run_args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    if let Some(header) = run_args.format.header() {
+        println!("{}", header);
+    }
+
+    for puzzle in puzzles {
+        let (origin, input) = read_input(puzzle, run_args.input.as_ref())?;
+        let input_checksum = checksum(&input);
+
+        let started = Instant::now();
+        let parsed = puzzle
+            .parse(&input)
+            .map_err(|err| err.render(&origin, &input))?;
+        let parse_elapsed = started.elapsed();
+
+        for part in &parts {
+            let started = Instant::now();
+            let answer = parsed.solve(*part);
+            let elapsed = parse_elapsed + started.elapsed();
+
+            let answer = Answer {
+                day: puzzle.day(),
+                part: *part,
+                answer,
+                checksum: input_checksum,
+                elapsed,
+            };
+            println!("{}", run_args.format.line(&answer));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_bench(bench_args: BenchArgs) -> Result<(), String> {
+    let puzzles = select_puzzles(bench_args.day)?;
+    let baseline = match &bench_args.baseline {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| format!("error: cannot read {}: {}", path.display(), err))
+            .and_then(|baseline| {
+                bench::parse_baseline(&baseline)
+                    .map_err(|err| format!("error: {}: {}", path.display(), err))
+            })?,
+        None => vec![],
+    };
+
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}  {:>9}",
+        "day", "phase", "min", "median", "p99", "change"
+    );
+
+    let mut measurements: Vec<Measurement> = vec![];
+    let mut regressions = 0;
+    for puzzle in puzzles {
+        let (origin, input) = read_input(puzzle, bench_args.input.as_ref())?;
+        let day_measurements = bench::measure(puzzle, &input, bench_args.iterations)
+            .map_err(|err| err.render(&origin, &input))?;
+
+        for m in &day_measurements {
+            let change = bench::change(m, &baseline);
+            let regressed = change.is_some_and(|c| c * 100.0 > bench_args.threshold);
+            regressions += usize::from(regressed);
+
+            println!(
+                "{:>3}  {:<5}  {:>12?}  {:>12?}  {:>12?}  {:>9}{}",
+                m.day,
+                m.phase,
+                m.stats.min,
+                m.stats.median,
+                m.stats.p99,
+                change.map_or("-".to_string(), |c| format!("{:+.1}%", c * 100.0)),
+                if regressed { "  REGRESSION" } else { "" }
+            );
+        }
+        measurements.extend(day_measurements);
+    }
+
+    if let Some(path) = &bench_args.save {
+        fs::write(path, bench::to_baseline(&measurements))
+            .map_err(|err| format!("error: cannot write {}: {}", path.display(), err))?;
+    }
+
+    match regressions {
+        0 => Ok(()),
+        n => Err(format!(
+            "error: {} phase(s) slower than the baseline by more than {}%",
+            n, bench_args.threshold
+        )),
+    }
+}
+
+fn generate(generate_args: GenerateArgs) -> Result<(), String> {
+    let generator = aoc::generator(generate_args.day)
+        .ok_or(format!("error: no generator for day {}", generate_args.day))?;
+
+    let input = generator(&mut Rng::new(generate_args.seed), generate_args.size);
+    print!("{}", input);
+
+    Ok(())
+}
+
+fn select_puzzles(day: Option<u8>) -> Result<Vec<&'static dyn Puzzle>, String> {
+    match day {
+        Some(day) => {
+            let puzzle = aoc::puzzle(day);
+            Ok(vec![
+                puzzle.ok_or(format!("error: day {} is not solved yet", day))?
+            ])
+        }
+        None => Ok(aoc::puzzles().to_vec()),
+    }
+}
+
+/// Reads the puzzle input together with a name used to point at it in diagnostics.
+fn read_input(
+    puzzle: &dyn Puzzle,
+    source: Option<&InputSource>,
+) -> Result<(String, String), String> {
+    match source {
+        None => Ok((
+            format!("day{}/input", puzzle.day()),
+            puzzle.input().to_string(),
+        )),
+        Some(InputSource::Stdin) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| format!("error: cannot read stdin: {}", err))?;
+            Ok(("<stdin>".to_string(), input))
+        }
+        Some(InputSource::Path(path)) => fs::read_to_string(path)
+            .map(|input| (path.display().to_string(), input))
+            .map_err(|err| format!("error: cannot read {}: {}", path.display(), err)),
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use aoc_core::Part;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+/// One solved part, as emitted by `aoc run`.
+#[derive(Debug, PartialEq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub checksum: u64,
+    /// Time to parse the input plus time to solve this part.
+    pub elapsed: Duration,
+}
+
+impl Format {
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            Format::Csv => Some("day,part,answer,checksum,elapsed_ns"),
+            Format::Text | Format::Json => None,
+        }
+    }
+
+    pub fn line(&self, answer: &Answer) -> String {
+        match self {
+            Format::Text => format!(
+                "day {} part {}: {}",
+                answer.day, answer.part, answer.answer
+            ),
+            Format::Json => format!(
+                "{{\"day\":{},\"part\":{},\"answer\":\"{}\",\"checksum\":\"{:016x}\",\"elapsed_ns\":{}}}",
+                answer.day,
+                answer.part,
+                json_escape(&answer.answer),
+                answer.checksum,
+                answer.elapsed.as_nanos()
+            ),
+            Format::Csv => format!(
+                "{},{},{},{:016x},{}",
+                answer.day,
+                answer.part,
+                csv_field(&answer.answer),
+                answer.checksum,
+                answer.elapsed.as_nanos()
+            ),
+        }
+    }
+}
+
+fn json_escape(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            c if c.is_control() => format!("\\u{:04x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(answer: &str) -> Answer {
+        Answer {
+            day: 5,
+            part: Part::Two,
+            answer: answer.to_string(),
+            checksum: 0xabc,
+            elapsed: Duration::from_micros(15),
+        }
+    }
+
+    #[test]
+    fn text_line() {
+        assert_eq!("day 5 part 2: MCD", Format::Text.line(&answer("MCD")));
+    }
+
+    #[test]
+    fn json_line() {
+        assert_eq!(
+            r#"{"day":5,"part":2,"answer":"MCD","checksum":"0000000000000abc","elapsed_ns":15000}"#,
+            Format::Json.line(&answer("MCD"))
+        );
+        assert_eq!(
+            r#"{"day":5,"part":2,"answer":"a\"b\\c","checksum":"0000000000000abc","elapsed_ns":15000}"#,
+            Format::Json.line(&answer("a\"b\\c"))
+        );
+    }
+
+    #[test]
+    fn csv_line() {
+        assert_eq!(
+            Some("day,part,answer,checksum,elapsed_ns"),
+            Format::Csv.header()
+        );
+        assert_eq!(
+            "5,2,MCD,0000000000000abc,15000",
+            Format::Csv.line(&answer("MCD"))
+        );
+        assert_eq!(
+            "5,2,\"a,\"\"b\",0000000000000abc,15000",
+            Format::Csv.line(&answer("a,\"b"))
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use std::{fs, path::Path};
+
+use aoc::golden::{parse_manifest, verify};
+
+#[test]
+fn every_day_matches_its_golden_answers() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+
+    let drifts: Vec<String> = aoc::puzzles()
+        .iter()
+        .flat_map(|puzzle| {
+            let path = workspace.join(format!("day{}/answers.txt", puzzle.day()));
+            let manifest = fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("cannot read {}: {}", path.display(), err));
+            let manifest = parse_manifest(&manifest)
+                .unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+
+            verify(*puzzle, puzzle.input(), &manifest)
+        })
+        .map(|drift| drift.to_string())
+        .collect();
+
+    assert!(drifts.is_empty(), "answers drifted:\n{}", drifts.join("\n"));
+}
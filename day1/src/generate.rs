@@ -0,0 +1,28 @@
+use aoc_core::Rng;
+
+/// Calorie inventory of `elves` elves, each carrying between one and ten snacks.
+pub fn generate(rng: &mut Rng, elves: usize) -> String {
+    (0..elves)
+        .map(|_| {
+            (0..rng.range(1..=10))
+                .map(|_| rng.range(1000..=60000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elves_calories;
+
+    #[test]
+    fn generated_inventory_parses() {
+        let inventory = generate(&mut Rng::new(1), 50);
+        assert_eq!(50, elves_calories(&inventory).unwrap().len());
+        assert_eq!(inventory, generate(&mut Rng::new(1), 50));
+    }
+}
@@ -0,0 +1,92 @@
+pub mod generate;
+pub mod parallel;
+pub mod rebalance;
+pub mod report;
+pub mod stream;
+
+use aoc_core::{ParseError, Solution};
+
+use stream::ElfTotals;
+pub use stream::{read_top_n, top_n};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        top_n(input, 3)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part_1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part_2(input)
+    }
+}
+
+pub fn elves_calories(file_content: &str) -> Result<Vec<u64>, ParseError> {
+    let mut output =
+        ElfTotals::new(file_content.lines().map(Ok)).collect::<Result<Vec<u64>, ParseError>>()?;
+
+    output.sort_by(|a, b| b.cmp(a));
+    Ok(output)
+}
+
+pub fn part_1(output: &[u64]) -> u64 {
+    *output.first().unwrap()
+}
+
+// Three u64 totals always fit in a u128.
+pub fn part_2(output: &[u64]) -> u128 {
+    output.iter().take(3).map(|total| u128::from(*total)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+    #[test]
+    fn calories_sorted_descending() {
+        assert_eq!(
+            Ok(vec![24000, 11000, 10000, 6000, 4000]),
+            elves_calories(DATA)
+        );
+    }
+
+    #[test]
+    fn solves_both_parts() {
+        let input = Day1::parse(DATA).unwrap();
+        assert_eq!(24000, Day1::part1(&input));
+        assert_eq!(45000, Day1::part2(&input));
+    }
+
+    #[test]
+    fn invalid_calories_are_reported_with_line() {
+        let err = elves_calories("1000\n\n20o0\n").unwrap_err();
+        assert_eq!(3, err.position().line);
+        assert_eq!("20o0", err.token());
+    }
+}
@@ -1,32 +1,15 @@
-use std::fs;
+use std::{fs::File, io::BufReader, process};
 
-fn main() {
-    let file_content = fs::read_to_string("input.txt").expect("read an input.txt");
+use day1::{part_1, part_2, read_top_n};
 
-    let mut output: Vec<u32> = file_content
-        .lines()
-        .fold(vec![vec![]], |mut acc, line| {
-            if line.is_empty() {
-                acc.push(Vec::new());
-            } else {
-                acc.last_mut().unwrap().push(line.parse::<u32>().unwrap());
-            }
-            acc
-        })
-        .iter()
-        .map(|elf_calories| elf_calories.iter().sum::<u32>())
-        .collect();
+fn main() {
+    let file = File::open("input.txt").expect("read an input.txt");
 
-    output.sort_by(|a, b| b.cmp(a));
+    let output = read_top_n(BufReader::new(file), 3).unwrap_or_else(|err| {
+        eprintln!("input.txt: {}", err);
+        process::exit(1)
+    });
 
     println!("Top 1 elf has {} calories", part_1(&output));
     println!("Top 3 elfs have {} calories", part_2(&output));
 }
-
-fn part_1(output: &[u32]) -> u32 {
-    *output.first().unwrap()
-}
-
-fn part_2(output: &[u32]) -> u32 {
-    output.iter().take(3).sum()
-}
@@ -0,0 +1,104 @@
+use std::thread;
+
+use aoc_core::ParseError;
+
+use crate::stream::{top_n_of, ElfTotals};
+
+/// Same as [`top_n`](crate::top_n), with the inventory split into up to `threads` chunks at
+/// empty lines and each chunk reduced to its own top `n` on a separate thread.
+pub fn par_top_n(file_content: &str, n: usize, threads: usize) -> Result<Vec<u64>, ParseError> {
+    let chunks = split_at_elves(file_content, threads.max(1));
+
+    let results: Vec<Result<Vec<u64>, ParseError>> = thread::scope(|scope| {
+        let workers: Vec<_> = chunks
+            .iter()
+            .map(|chunk| scope.spawn(move || top_n_of(ElfTotals::new(chunk.lines().map(Ok)), n)))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("chunk worker panicked"))
+            .collect()
+    });
+
+    if let Some(failed) = results.iter().position(Result::is_err) {
+        // Workers only know their own chunk; rerun the failed one numbering lines and elves
+        // from the start of the whole inventory.
+        let (line, elf) = chunks[..failed].iter().fold((1, 1), |(line, elf), chunk| {
+            (
+                line + chunk.lines().count() + 1,
+                elf + chunk.lines().filter(|line| line.is_empty()).count() + 1,
+            )
+        });
+        let err = ElfTotals::starting_at(chunks[failed].lines().map(Ok), line, elf)
+            .find_map(Result::<u64, ParseError>::err);
+        return Err(err.expect("chunk failed to parse before"));
+    }
+
+    let merged = results.into_iter().flat_map(Result::unwrap);
+    top_n_of(merged.map(Ok::<_, ParseError>), n)
+}
+
+/// Splits `file_content` into about `count` chunks of whole elves. The empty line separating
+/// two chunks belongs to neither of them.
+fn split_at_elves(file_content: &str, count: usize) -> Vec<&str> {
+    let target = file_content.len().div_ceil(count).max(1);
+    let mut chunks = vec![];
+    let mut rest = file_content;
+
+    while rest.len() > target {
+        match rest[target..].find("\n\n") {
+            Some(at) => {
+                let end = target + at + 1;
+                chunks.push(&rest[..end]);
+                rest = &rest[end + 1..];
+            }
+            None => break,
+        }
+    }
+    chunks.push(rest);
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Rng;
+
+    use super::*;
+    use crate::{generate::generate, top_n};
+
+    #[test]
+    fn same_answers_as_sequential() {
+        let inventory = generate(&mut Rng::new(5), 2000);
+        for threads in [1, 2, 3, 8, 64] {
+            for n in [1, 3, 10] {
+                assert_eq!(top_n(&inventory, n), par_top_n(&inventory, n, threads));
+            }
+        }
+    }
+
+    #[test]
+    fn split_keeps_every_elf_whole() {
+        let inventory = "1\n2\n\n3\n\n\n4\n\n";
+        for count in 1..=10 {
+            let chunks = split_at_elves(inventory, count);
+            assert_eq!(inventory, chunks.join("\n"));
+            for n in 0..6 {
+                assert_eq!(top_n(inventory, n), par_top_n(inventory, n, count));
+            }
+        }
+    }
+
+    #[test]
+    fn errors_point_into_the_whole_input() {
+        let mut inventory = generate(&mut Rng::new(9), 500);
+        inventory.push_str(&format!("\n{}\n1\n", u64::MAX));
+
+        for threads in [1, 4, 16] {
+            assert_eq!(top_n(&inventory, 3), par_top_n(&inventory, 3, threads));
+        }
+        assert!(top_n(&inventory, 3).is_err());
+
+        let broken = inventory.replacen("\n\n", "\n\nx\n", 300);
+        assert_eq!(top_n(&broken, 3), par_top_n(&broken, 3, 7));
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fmt,
+};
+
+use crate::report::Inventory;
+
+/// Inputs with at most this many items are solved exactly by [`Solver::Auto`].
+pub const EXACT_LIMIT: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Solver {
+    /// Branch and bound over every assignment; exponential in the number of items.
+    Exact,
+    /// Longest processing time first: each item, largest first, goes to the lightest elf.
+    Lpt,
+    Auto,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Quality {
+    Optimal,
+    /// The maximum load is at most this factor times the optimum.
+    WithinFactor(f64),
+    /// Every elf is under the requested limit; the maximum load is not minimised.
+    UnderLimit,
+}
+
+/// Item `index` of elf `from` handed over to elf `to`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub from: usize,
+    pub index: usize,
+    pub calories: u64,
+    pub to: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+    /// Load of elves `1..=k` once the moves are done; wider than an elf's total
+    /// since balancing over fewer elves can pile up more than `u64::MAX`.
+    pub loads: Vec<u128>,
+    pub max_load: u128,
+    /// No plan over the same elves can have a maximum load below this.
+    pub lower_bound: u128,
+    pub quality: Quality,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlanError {
+    ItemTooLarge { elf: usize, calories: u64 },
+    Infeasible { lower_bound: u128 },
+    NotFound { best: u128 },
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::ItemTooLarge { elf, calories } => write!(
+                f,
+                "elf #{} carries a single item of {} calories, above the limit",
+                elf, calories
+            ),
+            PlanError::Infeasible { lower_bound } => {
+                write!(f, "every plan has an elf carrying at least {}", lower_bound)
+            }
+            PlanError::NotFound { best } => write!(
+                f,
+                "no plan found under the limit, the best one peaks at {}",
+                best
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+#[derive(Clone, Copy, Debug)]
+struct Item {
+    elf: usize,
+    index: usize,
+    calories: u64,
+}
+
+fn items(inventory: &Inventory) -> Vec<Item> {
+    inventory
+        .elves()
+        .iter()
+        .flat_map(|elf| {
+            elf.items.iter().enumerate().map(|(index, calories)| Item {
+                elf: elf.number,
+                index,
+                calories: *calories,
+            })
+        })
+        .collect()
+}
+
+fn lower_bound(calories: impl Iterator<Item = u64> + Clone, k: usize) -> u128 {
+    let total: u128 = calories.clone().map(u128::from).sum();
+    let largest = calories.max().unwrap_or(0);
+    total.div_ceil(k as u128).max(u128::from(largest))
+}
+
+/// Redistributes every item over elves `1..=k` so the heaviest load is as small as possible.
+pub fn balance(inventory: &Inventory, k: usize, solver: Solver) -> Plan {
+    assert!(k > 0, "at least one elf has to carry the items");
+
+    let mut items = items(inventory);
+    items.sort_by_key(|item| Reverse(item.calories));
+    let calories: Vec<u64> = items.iter().map(|item| item.calories).collect();
+
+    let exact = match solver {
+        Solver::Exact => true,
+        Solver::Lpt => false,
+        Solver::Auto => items.len() <= EXACT_LIMIT,
+    };
+    let (bins, quality) = if exact {
+        (exact_partition(&calories, k), Quality::Optimal)
+    } else {
+        let factor = 4.0 / 3.0 - 1.0 / (3.0 * k as f64);
+        (lpt_partition(&calories, k), Quality::WithinFactor(factor))
+    };
+
+    let owners = label_bins(&items, &bins, k);
+    plan(
+        &items,
+        |i| owners[bins[i]],
+        k,
+        lower_bound(calories.iter().copied(), k),
+        quality,
+    )
+}
+
+/// Redistributes items among the existing elves so none carries more than `limit`,
+/// preferring to only unload the overloaded elves.
+pub fn cap(inventory: &Inventory, limit: u64, solver: Solver) -> Result<Plan, PlanError> {
+    let all_items = items(inventory);
+    let elves = inventory.elves().len();
+
+    if let Some(item) = all_items.iter().find(|item| item.calories > limit) {
+        return Err(PlanError::ItemTooLarge {
+            elf: item.elf,
+            calories: item.calories,
+        });
+    }
+    let bound = lower_bound(all_items.iter().map(|item| item.calories), elves);
+    if bound > u128::from(limit) {
+        return Err(PlanError::Infeasible { lower_bound: bound });
+    }
+
+    if let Some(plan) = unload_overloaded(inventory, &all_items, limit, bound) {
+        return Ok(plan);
+    }
+
+    let plan = balance(inventory, elves, solver);
+    match (plan.max_load <= u128::from(limit), &plan.quality) {
+        (true, _) => Ok(plan),
+        (false, Quality::Optimal) => Err(PlanError::Infeasible {
+            lower_bound: plan.max_load,
+        }),
+        (false, _) => Err(PlanError::NotFound {
+            best: plan.max_load,
+        }),
+    }
+}
+
+/// Takes the largest items off every elf above `limit` and hands each, largest first,
+/// to the fullest elf that still has room for it.
+fn unload_overloaded(
+    inventory: &Inventory,
+    items: &[Item],
+    limit: u64,
+    bound: u128,
+) -> Option<Plan> {
+    let limit = u128::from(limit);
+    let mut loads: Vec<u128> = inventory
+        .elves()
+        .iter()
+        .map(|elf| u128::from(elf.total))
+        .collect();
+    let mut owners: Vec<usize> = items.iter().map(|item| item.elf).collect();
+
+    let mut unloaded: Vec<usize> = vec![];
+    for elf in inventory
+        .elves()
+        .iter()
+        .filter(|elf| u128::from(elf.total) > limit)
+    {
+        let mut carried: Vec<usize> = (0..items.len())
+            .filter(|i| items[*i].elf == elf.number)
+            .collect();
+        carried.sort_by_key(|i| Reverse(items[*i].calories));
+
+        for i in carried {
+            if loads[elf.number - 1] <= limit {
+                break;
+            }
+            loads[elf.number - 1] -= u128::from(items[i].calories);
+            unloaded.push(i);
+        }
+    }
+
+    unloaded.sort_by_key(|i| Reverse(items[*i].calories));
+    for i in unloaded {
+        let target = (0..loads.len())
+            .filter(|elf| loads[*elf] + u128::from(items[i].calories) <= limit)
+            .max_by_key(|elf| loads[*elf])?;
+        loads[target] += u128::from(items[i].calories);
+        owners[i] = target + 1;
+    }
+
+    Some(plan(
+        items,
+        |i| owners[i],
+        loads.len(),
+        bound,
+        Quality::UnderLimit,
+    ))
+}
+
+fn plan(
+    items: &[Item],
+    owner: impl Fn(usize) -> usize,
+    k: usize,
+    lower_bound: u128,
+    quality: Quality,
+) -> Plan {
+    let mut loads = vec![0u128; k];
+    let mut moves = vec![];
+    for (i, item) in items.iter().enumerate() {
+        let to = owner(i);
+        loads[to - 1] += u128::from(item.calories);
+        if to != item.elf {
+            moves.push(Move {
+                from: item.elf,
+                index: item.index,
+                calories: item.calories,
+                to,
+            });
+        }
+    }
+    moves.sort_by_key(|m| (m.from, m.index));
+
+    Plan {
+        max_load: loads.iter().copied().max().unwrap_or(0),
+        moves,
+        loads,
+        lower_bound,
+        quality,
+    }
+}
+
+/// Picks which elf receives each bin, so as many calories as possible stay where they are.
+fn label_bins(items: &[Item], bins: &[usize], k: usize) -> Vec<usize> {
+    let mut overlap: HashMap<(usize, usize), u128> = HashMap::new();
+    for (item, bin) in items.iter().zip(bins) {
+        if item.elf <= k && item.calories > 0 {
+            *overlap.entry((*bin, item.elf - 1)).or_default() += u128::from(item.calories);
+        }
+    }
+
+    let mut pairs: Vec<(u128, usize, usize)> = overlap
+        .into_iter()
+        .map(|((bin, elf), weight)| (weight, bin, elf))
+        .collect();
+    pairs.sort_by_key(|(weight, bin, elf)| (Reverse(*weight), *bin, *elf));
+
+    let mut owners = vec![0; k];
+    let mut taken = vec![false; k];
+    for (_, bin, elf) in pairs {
+        if owners[bin] == 0 && !taken[elf] {
+            owners[bin] = elf + 1;
+            taken[elf] = true;
+        }
+    }
+
+    // Bins sharing nothing with a free elf go to the free elves in order.
+    let mut free = (0..k).filter(|elf| !taken[*elf]);
+    for owner in owners.iter_mut().filter(|owner| **owner == 0) {
+        *owner = free.next().expect("as many elves as bins") + 1;
+    }
+    owners
+}
+
+/// Bin of every item for items sorted by decreasing calories.
+fn lpt_partition(calories: &[u64], k: usize) -> Vec<usize> {
+    let mut heap: BinaryHeap<Reverse<(u128, usize)>> =
+        (0..k).map(|bin| Reverse((0, bin))).collect();
+
+    calories
+        .iter()
+        .map(|calories| {
+            let Reverse((load, bin)) = heap.pop().unwrap();
+            heap.push(Reverse((load + u128::from(*calories), bin)));
+            bin
+        })
+        .collect()
+}
+
+fn exact_partition(calories: &[u64], k: usize) -> Vec<usize> {
+    struct Search<'a> {
+        calories: &'a [u64],
+        loads: Vec<u128>,
+        current: Vec<usize>,
+        best: Vec<usize>,
+        best_max: u128,
+        lower_bound: u128,
+    }
+
+    impl Search<'_> {
+        fn assign(&mut self, i: usize) {
+            if self.best_max == self.lower_bound {
+                return;
+            }
+            if i == self.calories.len() {
+                self.best_max = self.loads.iter().copied().max().unwrap_or(0);
+                self.best.clone_from(&self.current);
+                return;
+            }
+
+            for bin in 0..self.loads.len() {
+                let load = self.loads[bin] + u128::from(self.calories[i]);
+                // Bins with equal loads are interchangeable, trying the first one is enough.
+                if load >= self.best_max || self.loads[..bin].contains(&self.loads[bin]) {
+                    continue;
+                }
+                self.loads[bin] = load;
+                self.current[i] = bin;
+                self.assign(i + 1);
+                self.loads[bin] -= u128::from(self.calories[i]);
+            }
+        }
+    }
+
+    let best = lpt_partition(calories, k);
+    let mut loads = vec![0u128; k];
+    calories
+        .iter()
+        .zip(&best)
+        .for_each(|(calories, bin)| loads[*bin] += u128::from(*calories));
+
+    let mut search = Search {
+        calories,
+        loads: vec![0; k],
+        current: vec![0; calories.len()],
+        best_max: loads.into_iter().max().unwrap_or(0),
+        best,
+        lower_bound: lower_bound(calories.iter().copied(), k),
+    };
+    search.assign(0);
+    search.best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory(data: &str) -> Inventory {
+        Inventory::parse(data).unwrap()
+    }
+
+    fn apply(inventory: &Inventory, plan: &Plan) -> Vec<u128> {
+        let mut loads = vec![0; plan.loads.len().max(inventory.elves().len())];
+        for elf in inventory.elves() {
+            for (index, calories) in elf.items.iter().enumerate() {
+                let to = plan
+                    .moves
+                    .iter()
+                    .find(|m| m.from == elf.number && m.index == index)
+                    .map_or(elf.number, |m| m.to);
+                loads[to - 1] += u128::from(*calories);
+            }
+        }
+        loads
+    }
+
+    #[test]
+    fn exact_beats_lpt_on_classic_instance() {
+        // LPT puts 3+2+2 together while 3+3 | 2+2+2 is optimal.
+        let inventory = inventory("3\n3\n2\n2\n2");
+
+        let exact = balance(&inventory, 2, Solver::Exact);
+        assert_eq!(6, exact.max_load);
+        assert_eq!(Quality::Optimal, exact.quality);
+
+        let lpt = balance(&inventory, 2, Solver::Lpt);
+        assert_eq!(7, lpt.max_load);
+        match lpt.quality {
+            Quality::WithinFactor(factor) => assert!(lpt.max_load as f64 <= 6.0 * factor + 1e-9),
+            other => panic!("expected an approximation bound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn moves_reproduce_loads() {
+        let inventory = inventory("9\n1\n\n5\n\n2\n2\n\n8\n3\n4");
+        for solver in [Solver::Exact, Solver::Lpt] {
+            let plan = balance(&inventory, 3, solver);
+            let loads = apply(&inventory, &plan);
+
+            assert_eq!(plan.loads, loads[..3]);
+            assert!(loads[3..].iter().all(|load| *load == 0));
+            assert!(plan.max_load >= plan.lower_bound);
+        }
+    }
+
+    #[test]
+    fn balanced_inventory_needs_no_moves() {
+        let inventory = inventory("4\n\n4\n\n4");
+        assert!(balance(&inventory, 3, Solver::Auto).moves.is_empty());
+    }
+
+    #[test]
+    fn cap_only_unloads_overloaded_elves() {
+        let inventory = inventory("4\n3\n\n1\n\n2");
+        let plan = cap(&inventory, 5, Solver::Auto).unwrap();
+
+        assert_eq!(
+            vec![Move {
+                from: 1,
+                index: 0,
+                calories: 4,
+                to: 2
+            }],
+            plan.moves
+        );
+        assert_eq!(vec![3, 5, 2], plan.loads);
+        assert_eq!(plan.loads, apply(&inventory, &plan));
+    }
+
+    #[test]
+    fn cap_falls_back_to_balancing() {
+        let inventory = inventory("6\n5\n\n1\n\n2");
+        let plan = cap(&inventory, 6, Solver::Auto).unwrap();
+
+        assert_eq!(6, plan.max_load);
+        assert_eq!(Quality::Optimal, plan.quality);
+        assert_eq!(plan.loads, apply(&inventory, &plan));
+    }
+
+    #[test]
+    fn cap_reports_impossible_limits() {
+        assert_eq!(
+            Err(PlanError::ItemTooLarge {
+                elf: 1,
+                calories: 6
+            }),
+            cap(&inventory("6\n5\n\n1"), 5, Solver::Auto)
+        );
+        assert_eq!(
+            Err(PlanError::Infeasible { lower_bound: 6 }),
+            cap(&inventory("5\n5\n\n2"), 5, Solver::Auto)
+        );
+    }
+
+    #[test]
+    fn loads_beyond_u64_do_not_overflow() {
+        let half = u64::MAX / 2 + 1;
+        let halves = inventory(&format!("{}\n\n{}", half, half));
+        for solver in [Solver::Exact, Solver::Lpt] {
+            let plan = balance(&halves, 1, solver);
+            assert_eq!(2 * u128::from(half), plan.max_load);
+            assert_eq!(plan.max_load, plan.lower_bound);
+            assert_eq!(plan.loads, apply(&halves, &plan)[..1]);
+        }
+
+        // Unloading tries `2 + (u64::MAX - 1)` before falling back to balancing.
+        let full = inventory(&format!("{}\n1\n\n2", u64::MAX - 1));
+        let plan = cap(&full, u64::MAX - 1, Solver::Auto).unwrap();
+        assert_eq!(u128::from(u64::MAX - 1), plan.max_load);
+        assert_eq!(plan.loads, apply(&full, &plan));
+    }
+
+    #[test]
+    fn many_elves_are_labelled_without_a_square_table() {
+        let k = 100_000;
+        let data = (1..=k)
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let inventory = inventory(&data);
+
+        let plan = balance(&inventory, k, Solver::Lpt);
+        assert!(plan.moves.is_empty());
+        assert_eq!(plan.loads, apply(&inventory, &plan));
+    }
+}
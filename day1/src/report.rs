@@ -0,0 +1,239 @@
+use aoc_core::{parse_number, ParseError};
+
+use crate::stream::add;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Elf {
+    /// 1-based position of the elf in the inventory.
+    pub number: usize,
+    pub items: Vec<u64>,
+    pub total: u64,
+}
+
+impl Elf {
+    pub fn item_count(&self) -> usize {
+        self.items.len()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation of the totals.
+    pub std_dev: f64,
+}
+
+/// Elves whose totals fall in `start..end`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bucket {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+/// Every elf with its items, in the order they appear in the input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inventory {
+    elves: Vec<Elf>,
+}
+
+impl Inventory {
+    pub fn parse(file_content: &str) -> Result<Inventory, ParseError> {
+        let mut elves = vec![Elf {
+            number: 1,
+            items: vec![],
+            total: 0,
+        }];
+
+        for (index, line) in file_content.lines().enumerate() {
+            if line.is_empty() {
+                elves.push(Elf {
+                    number: elves.len() + 1,
+                    items: vec![],
+                    total: 0,
+                });
+                continue;
+            }
+
+            let elf = elves.last_mut().unwrap();
+            let calories = parse_number::<u64>(line).map_err(|err| err.on_line(index + 1))?;
+            elf.total =
+                add(elf.total, calories, line, elf.number).map_err(|err| err.on_line(index + 1))?;
+            elf.items.push(calories);
+        }
+
+        Ok(Inventory { elves })
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    pub fn elf(&self, number: usize) -> Option<&Elf> {
+        number
+            .checked_sub(1)
+            .and_then(|index| self.elves.get(index))
+    }
+
+    pub fn statistics(&self) -> Option<Statistics> {
+        let mut totals: Vec<u64> = self.elves.iter().map(|elf| elf.total).collect();
+        totals.sort();
+        if totals.is_empty() {
+            return None;
+        }
+
+        let count = totals.len();
+        let mean = totals.iter().map(|t| u128::from(*t)).sum::<u128>() as f64 / count as f64;
+        let median = match count % 2 {
+            0 => (totals[count / 2 - 1] as f64 + totals[count / 2] as f64) / 2.0,
+            _ => totals[count / 2] as f64,
+        };
+        let variance = totals
+            .iter()
+            .map(|t| (*t as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Some(Statistics {
+            count,
+            min: totals[0],
+            max: totals[count - 1],
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+        })
+    }
+
+    /// Counts of elves per `width`-sized range of totals, from the lowest to the highest
+    /// total, empty ranges included.
+    pub fn histogram(&self, width: u64) -> Vec<Bucket> {
+        assert!(width > 0, "bucket width must be positive");
+        let Some(stats) = self.statistics() else {
+            return vec![];
+        };
+
+        let first = stats.min / width;
+        let last = stats.max / width;
+        (first..=last)
+            .map(|bucket| Bucket {
+                start: bucket * width,
+                end: bucket.saturating_mul(width).saturating_add(width),
+                count: self
+                    .elves
+                    .iter()
+                    .filter(|elf| elf.total / width == bucket)
+                    .count(),
+            })
+            .collect()
+    }
+
+    /// Elves carrying more than `threshold` calories, in inventory order.
+    pub fn above(&self, threshold: u64) -> Vec<&Elf> {
+        self.elves
+            .iter()
+            .filter(|elf| elf.total > threshold)
+            .collect()
+    }
+
+    /// Rank of elf `number` by total, 1 being the best stocked. Elves with equal totals
+    /// share a rank.
+    pub fn rank(&self, number: usize) -> Option<usize> {
+        let total = self.elf(number)?.total;
+        Some(self.elves.iter().filter(|elf| elf.total > total).count() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn keeps_elves_in_input_order() {
+        let inventory = Inventory::parse(DATA).unwrap();
+        let summary: Vec<(usize, usize, u64)> = inventory
+            .elves()
+            .iter()
+            .map(|elf| (elf.number, elf.item_count(), elf.total))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (1, 3, 6000),
+                (2, 1, 4000),
+                (3, 2, 11000),
+                (4, 3, 24000),
+                (5, 1, 10000)
+            ],
+            summary
+        );
+    }
+
+    #[test]
+    fn aggregate_statistics() {
+        let stats = Inventory::parse(DATA).unwrap().statistics().unwrap();
+
+        assert_eq!(5, stats.count);
+        assert_eq!(4000, stats.min);
+        assert_eq!(24000, stats.max);
+        assert_eq!(11000.0, stats.mean);
+        assert_eq!(10000.0, stats.median);
+        assert!((stats.std_dev - 6985.6997).abs() < 1e-3);
+    }
+
+    #[test]
+    fn histogram_covers_every_total() {
+        let histogram = Inventory::parse(DATA).unwrap().histogram(10000);
+
+        assert_eq!(
+            vec![
+                Bucket {
+                    start: 0,
+                    end: 10000,
+                    count: 2
+                },
+                Bucket {
+                    start: 10000,
+                    end: 20000,
+                    count: 2
+                },
+                Bucket {
+                    start: 20000,
+                    end: 30000,
+                    count: 1
+                },
+            ],
+            histogram
+        );
+    }
+
+    #[test]
+    fn threshold_and_rank_queries() {
+        let inventory = Inventory::parse(DATA).unwrap();
+
+        assert_eq!(
+            vec![3, 4],
+            inventory
+                .above(10000)
+                .iter()
+                .map(|elf| elf.number)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(1), inventory.rank(4));
+        assert_eq!(Some(5), inventory.rank(2));
+        assert_eq!(None, inventory.rank(6));
+        assert_eq!(None, inventory.rank(0));
+    }
+
+    #[test]
+    fn equal_totals_share_rank() {
+        let inventory = Inventory::parse("5\n\n7\n\n5").unwrap();
+        assert_eq!(Some(2), inventory.rank(1));
+        assert_eq!(Some(2), inventory.rank(3));
+    }
+}
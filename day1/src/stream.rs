@@ -0,0 +1,203 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt,
+    io::{self, BufRead},
+};
+
+use aoc_core::{parse_number, ParseError};
+
+#[derive(Debug)]
+pub enum InventoryError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InventoryError::Io(err) => write!(f, "cannot read inventory: {}", err),
+            InventoryError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for InventoryError {}
+
+impl From<io::Error> for InventoryError {
+    fn from(err: io::Error) -> Self {
+        InventoryError::Io(err)
+    }
+}
+
+impl From<ParseError> for InventoryError {
+    fn from(err: ParseError) -> Self {
+        InventoryError::Parse(err)
+    }
+}
+
+/// Calorie total of every elf, read one line at a time. Each empty line starts a new elf,
+/// so only the running total of the current elf is kept in memory.
+pub struct ElfTotals<I> {
+    lines: I,
+    line_no: usize,
+    elf: usize,
+    done: bool,
+}
+
+impl<I> ElfTotals<I> {
+    pub fn new(lines: I) -> Self {
+        Self::starting_at(lines, 1, 1)
+    }
+
+    /// Totals of a slice of a larger inventory whose first line is `line` of the whole
+    /// input and holds items of elf number `elf`, so errors point into the whole input.
+    pub fn starting_at(lines: I, line: usize, elf: usize) -> Self {
+        ElfTotals {
+            lines,
+            line_no: line - 1,
+            elf: elf - 1,
+            done: false,
+        }
+    }
+}
+
+impl<I, L, E> Iterator for ElfTotals<I>
+where
+    I: Iterator<Item = Result<L, E>>,
+    L: AsRef<str>,
+    E: From<ParseError>,
+{
+    type Item = Result<u64, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.elf += 1;
+        let mut total: u64 = 0;
+        loop {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(err)) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+                None => {
+                    self.done = true;
+                    return Some(Ok(total));
+                }
+            };
+            self.line_no += 1;
+
+            let line = line.as_ref();
+            if line.is_empty() {
+                return Some(Ok(total));
+            }
+            match parse_number::<u64>(line)
+                .and_then(|calories| add(total, calories, line, self.elf))
+            {
+                Ok(sum) => total = sum,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err.on_line(self.line_no).into()));
+                }
+            }
+        }
+    }
+}
+
+/// Adds the calories on `line` to the running total of elf number `elf`.
+pub(crate) fn add(total: u64, calories: u64, line: &str, elf: usize) -> Result<u64, ParseError> {
+    total.checked_add(calories).ok_or_else(|| {
+        let reason = format!("calorie total of elf #{} exceeds {}", elf, u64::MAX);
+        ParseError::overflow(line, reason)
+    })
+}
+
+/// The `n` highest totals, highest first, keeping at most `n` totals in memory.
+pub fn top_n_of<E>(totals: impl Iterator<Item = Result<u64, E>>, n: usize) -> Result<Vec<u64>, E> {
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for total in totals {
+        heap.push(Reverse(total?));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(total)| total)
+        .collect())
+}
+
+pub fn top_n(file_content: &str, n: usize) -> Result<Vec<u64>, ParseError> {
+    top_n_of(ElfTotals::new(file_content.lines().map(Ok)), n)
+}
+
+pub fn read_top_n<R: BufRead>(reader: R, n: usize) -> Result<Vec<u64>, InventoryError> {
+    top_n_of(
+        ElfTotals::new(
+            reader
+                .lines()
+                .map(|line| line.map_err(InventoryError::from)),
+        ),
+        n,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::elves_calories;
+
+    const DATA: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    #[test]
+    fn top_n_is_prefix_of_sorted_totals() {
+        let sorted = elves_calories(DATA).unwrap();
+        for n in 0..=7 {
+            assert_eq!(
+                sorted.iter().take(n).copied().collect::<Vec<_>>(),
+                top_n(DATA, n).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn read_from_buffered_reader() {
+        assert_eq!(
+            vec![24000, 11000, 10000],
+            read_top_n(Cursor::new(DATA), 3).unwrap()
+        );
+
+        match read_top_n(Cursor::new("1000\n\nabc\n"), 3) {
+            Err(InventoryError::Parse(err)) => assert_eq!(3, err.position().line),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn totals_beyond_u32_are_kept() {
+        let inventory = format!("{}\n{}\n\n1", u32::MAX, u32::MAX);
+        assert_eq!(
+            vec![2 * u64::from(u32::MAX), 1],
+            top_n(&inventory, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn overflow_names_the_elf() {
+        let inventory = format!("1\n\n{}\n1\n", u64::MAX);
+        let err = top_n(&inventory, 3).unwrap_err();
+
+        assert_eq!(4, err.position().line);
+        assert!(
+            matches!(err, ParseError::Overflow { ref reason, .. } if reason.contains("elf #2"))
+        );
+    }
+}
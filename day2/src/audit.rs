@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+use aoc_core::ParseError;
+
+use crate::{battle, parse_battle_log, BattleResult, HandGesture, LogParser, Scorer};
+
+/// How a single line of the log was read and scored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditedRound {
+    /// 1-based line in the log.
+    pub line: usize,
+    pub enemy: HandGesture,
+    pub you: HandGesture,
+    pub result: BattleResult,
+    pub gesture_score: u32,
+    pub outcome_score: u32,
+}
+
+impl AuditedRound {
+    pub fn score(&self) -> u32 {
+        self.gesture_score + self.outcome_score
+    }
+}
+
+/// Rounds and total score for one outcome or gesture.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub rounds: usize,
+    pub score: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub by_outcome: Vec<(BattleResult, Tally)>,
+    /// Keyed by the gesture you played.
+    pub by_gesture: Vec<(HandGesture, Tally)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Audit {
+    pub rounds: Vec<AuditedRound>,
+}
+
+impl Audit {
+    pub fn new(battle_log: &str, parser: &dyn LogParser) -> Result<Audit, ParseError> {
+        let rounds = parse_battle_log(battle_log, parser)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, (enemy, you))| {
+                let result = battle(enemy.clone(), you.clone());
+                AuditedRound {
+                    line: index + 1,
+                    gesture_score: you.score(),
+                    outcome_score: result.score(),
+                    enemy,
+                    you,
+                    result,
+                }
+            })
+            .collect();
+        Ok(Audit { rounds })
+    }
+
+    /// Always equal to what [`crate::calculate_result`] returns for the same log.
+    pub fn total(&self) -> u32 {
+        self.rounds.iter().map(AuditedRound::score).sum()
+    }
+
+    pub fn summary(&self) -> Summary {
+        let tally = |keep: &dyn Fn(&AuditedRound) -> bool| {
+            self.rounds
+                .iter()
+                .filter(|round| keep(round))
+                .fold(Tally::default(), |tally, round| Tally {
+                    rounds: tally.rounds + 1,
+                    score: tally.score + round.score(),
+                })
+        };
+        Summary {
+            by_outcome: [BattleResult::Win, BattleResult::Draw, BattleResult::Lose]
+                .into_iter()
+                .map(|result| (result, tally(&|round| round.result == result)))
+                .collect(),
+            by_gesture: [HandGesture::Rock, HandGesture::Paper, HandGesture::Scissors]
+                .into_iter()
+                .map(|gesture| {
+                    let tally = tally(&|round| round.you == gesture);
+                    (gesture, tally)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn rounds_csv(&self) -> String {
+        let mut csv = String::from("line,enemy,you,result,gesture_score,outcome_score,score\n");
+        for round in &self.rounds {
+            writeln!(
+                csv,
+                "{},{:?},{:?},{:?},{},{},{}",
+                round.line,
+                round.enemy,
+                round.you,
+                round.result,
+                round.gesture_score,
+                round.outcome_score,
+                round.score()
+            )
+            .expect("writing to a String cannot fail");
+        }
+        csv
+    }
+}
+
+impl Summary {
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("group,key,rounds,score\n");
+        let outcomes = self
+            .by_outcome
+            .iter()
+            .map(|(result, tally)| ("outcome", format!("{:?}", result), tally));
+        let gestures = self
+            .by_gesture
+            .iter()
+            .map(|(gesture, tally)| ("gesture", format!("{:?}", gesture), tally));
+        for (group, key, tally) in outcomes.chain(gestures) {
+            writeln!(csv, "{},{},{},{}", group, key, tally.rounds, tally.score)
+                .expect("writing to a String cannot fail");
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_result, Part1Parser, Part2Parser};
+
+    const SAMPLE: &str = "A Y\nB X\nC Z";
+
+    #[test]
+    fn rounds_show_where_the_points_come_from() {
+        let audit = Audit::new(SAMPLE, &Part1Parser).unwrap();
+        assert_eq!(
+            AuditedRound {
+                line: 2,
+                enemy: HandGesture::Paper,
+                you: HandGesture::Rock,
+                result: BattleResult::Lose,
+                gesture_score: 1,
+                outcome_score: 0,
+            },
+            audit.rounds[1]
+        );
+        assert_eq!(
+            "line,enemy,you,result,gesture_score,outcome_score,score\n\
+             1,Rock,Paper,Win,2,6,8\n\
+             2,Paper,Rock,Lose,1,0,1\n\
+             3,Scissors,Scissors,Draw,3,3,6\n",
+            audit.rounds_csv()
+        );
+    }
+
+    #[test]
+    fn summary_groups_by_outcome_and_gesture() {
+        let summary = Audit::new(SAMPLE, &Part2Parser).unwrap().summary();
+        assert_eq!(
+            "group,key,rounds,score\n\
+             outcome,Win,1,7\n\
+             outcome,Draw,1,4\n\
+             outcome,Lose,1,1\n\
+             gesture,Rock,3,12\n\
+             gesture,Paper,0,0\n\
+             gesture,Scissors,0,0\n",
+            summary.to_csv()
+        );
+    }
+
+    #[test]
+    fn audit_total_matches_calculate_result() {
+        let input = include_str!("../input.txt");
+        for parser in [&Part1Parser as &dyn LogParser, &Part2Parser] {
+            let audit = Audit::new(input, parser).unwrap();
+            let summary = audit.summary();
+            let outcome_total: u32 = summary.by_outcome.iter().map(|(_, t)| t.score).sum();
+            let gesture_total: u32 = summary.by_gesture.iter().map(|(_, t)| t.score).sum();
+            assert_eq!(outcome_total, audit.total());
+            assert_eq!(gesture_total, audit.total());
+        }
+        assert_eq!(
+            calculate_result(input, Box::new(Part2Parser)),
+            Ok(Audit::new(input, &Part2Parser).unwrap().total())
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use aoc_core::ParseError;
+
+use crate::{BattleResult, HandGesture, LogParser, Round};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum YourColumn {
+    Gesture(HashMap<String, HandGesture>),
+    Outcome(HashMap<String, BattleResult>),
+}
+
+/// A [`LogParser`] described by a config file instead of code:
+///
+/// ```text
+/// # comment
+/// separator = " "
+/// columns = enemy you
+/// enemy = A:Rock B:Paper C:Scissors
+/// outcome = X:Lose Y:Draw Z:Win
+/// ```
+///
+/// Use `you = X:Rock ...` instead of `outcome` when the second column names your gesture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncodedParser {
+    separator: String,
+    enemy_first: bool,
+    enemy: HashMap<String, HandGesture>,
+    you: YourColumn,
+}
+
+impl EncodedParser {
+    pub fn from_config(config: &str) -> Result<EncodedParser, ParseError> {
+        let mut separator = None;
+        let mut enemy_first = None;
+        let mut enemy = None;
+        let mut you = None;
+
+        for (index, line) in config.lines().enumerate() {
+            let line_error = |err: ParseError| err.on_line(index + 1);
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| line_error(ParseError::missing(line, "`=` after the key")))?;
+            let (key, value) = (key.trim(), value.trim());
+            let duplicate =
+                || line_error(ParseError::unexpected(key, "each key only once").within(line, key));
+
+            match key {
+                "separator" => {
+                    let unquoted = value
+                        .strip_prefix('"')
+                        .and_then(|value| value.strip_suffix('"'))
+                        .filter(|value| !value.is_empty())
+                        .ok_or_else(|| {
+                            line_error(
+                                ParseError::unexpected(value, "a quoted separator like \" \"")
+                                    .within(line, value),
+                            )
+                        })?;
+                    if separator.replace(unquoted.to_string()).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                "columns" => {
+                    let order = match value.split_whitespace().collect::<Vec<_>>()[..] {
+                        ["enemy", "you"] => true,
+                        ["you", "enemy"] => false,
+                        _ => {
+                            return Err(line_error(
+                                ParseError::unexpected(value, "`enemy you` or `you enemy`")
+                                    .within(line, value),
+                            ))
+                        }
+                    };
+                    if enemy_first.replace(order).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                "enemy" => {
+                    let mapping = mapping(line, value, gesture, "Rock, Paper or Scissors")
+                        .map_err(line_error)?;
+                    if enemy.replace(mapping).is_some() {
+                        return Err(duplicate());
+                    }
+                }
+                "you" | "outcome" => {
+                    let column = match key {
+                        "you" => YourColumn::Gesture(
+                            mapping(line, value, gesture, "Rock, Paper or Scissors")
+                                .map_err(line_error)?,
+                        ),
+                        _ => YourColumn::Outcome(
+                            mapping(line, value, outcome, "Lose, Draw or Win")
+                                .map_err(line_error)?,
+                        ),
+                    };
+                    if you.is_some() {
+                        return Err(line_error(
+                            ParseError::unexpected(key, "only one of `you` or `outcome`")
+                                .within(line, key),
+                        ));
+                    }
+                    you = Some(column);
+                }
+                _ => {
+                    return Err(line_error(
+                        ParseError::unexpected(key, "separator, columns, enemy, you or outcome")
+                            .within(line, key),
+                    ))
+                }
+            }
+        }
+
+        let end_of_config =
+            |what: &str| ParseError::missing("", what).on_line(config.lines().count() + 1);
+        Ok(EncodedParser {
+            separator: separator.unwrap_or_else(|| " ".to_string()),
+            enemy_first: enemy_first.unwrap_or(true),
+            enemy: enemy.ok_or_else(|| end_of_config("an `enemy` mapping"))?,
+            you: you.ok_or_else(|| end_of_config("a `you` or `outcome` mapping"))?,
+        })
+    }
+}
+
+fn gesture(name: &str) -> Option<HandGesture> {
+    match name {
+        "Rock" => Some(HandGesture::Rock),
+        "Paper" => Some(HandGesture::Paper),
+        "Scissors" => Some(HandGesture::Scissors),
+        _ => None,
+    }
+}
+
+fn outcome(name: &str) -> Option<BattleResult> {
+    match name {
+        "Lose" => Some(BattleResult::Lose),
+        "Draw" => Some(BattleResult::Draw),
+        "Win" => Some(BattleResult::Win),
+        _ => None,
+    }
+}
+
+/// Reads `code:Name` pairs separated by whitespace; `names` lists what `lookup` accepts.
+fn mapping<T>(
+    line: &str,
+    value: &str,
+    lookup: fn(&str) -> Option<T>,
+    names: &str,
+) -> Result<HashMap<String, T>, ParseError> {
+    let mut codes = HashMap::new();
+    for pair in value.split_whitespace() {
+        let (code, name) = pair
+            .split_once(':')
+            .filter(|(code, _)| !code.is_empty())
+            .ok_or_else(|| ParseError::unexpected(pair, "a `code:Name` pair").within(line, pair))?;
+        let meaning =
+            lookup(name).ok_or_else(|| ParseError::unexpected(name, names).within(line, name))?;
+        if codes.insert(code.to_string(), meaning).is_some() {
+            return Err(ParseError::unexpected(code, "each code only once").within(line, code));
+        }
+    }
+    Ok(codes)
+}
+
+impl LogParser for EncodedParser {
+    fn parse_log(&self, log: &str) -> Result<Round, ParseError> {
+        let mut columns = log.split(self.separator.as_str());
+        let first = columns
+            .next()
+            .filter(|first| !first.is_empty())
+            .ok_or_else(|| ParseError::missing(log, "a first column"))?;
+        let second = columns
+            .next()
+            .ok_or_else(|| ParseError::missing(log, "a second column"))?;
+        if let Some(extra) = columns.next() {
+            return Err(ParseError::unexpected(extra, "end of line").within(log, extra));
+        }
+        let (enemy_code, your_code) = if self.enemy_first {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let unknown = |code: &str, codes: Vec<&String>| {
+            let mut codes: Vec<&str> = codes.into_iter().map(String::as_str).collect();
+            codes.sort();
+            ParseError::unexpected(code, format!("one of {}", codes.join(", "))).within(log, code)
+        };
+        let enemy = self
+            .enemy
+            .get(enemy_code)
+            .cloned()
+            .ok_or_else(|| unknown(enemy_code, self.enemy.keys().collect()))?;
+        let you = match &self.you {
+            YourColumn::Gesture(codes) => codes
+                .get(your_code)
+                .cloned()
+                .ok_or_else(|| unknown(your_code, codes.keys().collect()))?,
+            YourColumn::Outcome(codes) => codes
+                .get(your_code)
+                .ok_or_else(|| unknown(your_code, codes.keys().collect()))?
+                .when_enemy(enemy.clone()),
+        };
+
+        Ok((enemy, you))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Position;
+
+    use super::*;
+    use crate::{calculate_result, Part1Parser, Part2Parser};
+
+    const PART_1: &str = include_str!("../encodings/part1.cfg");
+    const PART_2: &str = include_str!("../encodings/part2.cfg");
+    const INPUT: &str = include_str!("../input.txt");
+
+    #[test]
+    fn shipped_configs_match_builtin_parsers() {
+        let part1 = EncodedParser::from_config(PART_1).unwrap();
+        let part2 = EncodedParser::from_config(PART_2).unwrap();
+
+        assert_eq!(
+            calculate_result(INPUT, Box::new(Part1Parser)),
+            calculate_result(INPUT, Box::new(part1))
+        );
+        assert_eq!(
+            calculate_result(INPUT, Box::new(Part2Parser)),
+            calculate_result(INPUT, Box::new(part2))
+        );
+    }
+
+    #[test]
+    fn custom_separator_and_column_order() {
+        let parser = EncodedParser::from_config(
+            "separator = \",\"
+columns = you enemy
+enemy = r:Rock p:Paper s:Scissors
+outcome = l:Lose d:Draw w:Win",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Ok(12),
+            calculate_result("d,r\nl,p\nw,s", Box::new(parser.clone()))
+        );
+
+        let err = parser.parse_log("d,x").unwrap_err();
+        assert_eq!(Position { line: 1, column: 3 }, err.position());
+        assert!(err.to_string().ends_with("expected one of p, r, s"));
+    }
+
+    #[test]
+    fn config_errors_point_at_the_line() {
+        let err = EncodedParser::from_config("enemy = A:Rock\nyou = X:Stone").unwrap_err();
+        assert_eq!(Position { line: 2, column: 9 }, err.position());
+        assert_eq!("Stone", err.token());
+
+        assert!(err
+            .to_string()
+            .ends_with("expected Rock, Paper or Scissors"));
+
+        let err = EncodedParser::from_config("enemy = A:Rock\noutcome = X:Rock").unwrap_err();
+        assert!(err.to_string().ends_with("expected Lose, Draw or Win"));
+
+        let err = EncodedParser::from_config("enemy = A:Rock A:Paper").unwrap_err();
+        assert_eq!(
+            Position {
+                line: 1,
+                column: 16
+            },
+            err.position()
+        );
+        assert_eq!("A", err.token());
+
+        let err = EncodedParser::from_config("enemy = A:Rock\ncolour = red").unwrap_err();
+        assert_eq!("colour", err.token());
+
+        let err = EncodedParser::from_config("enemy = A:Rock").unwrap_err();
+        assert_eq!(2, err.position().line);
+
+        let err = EncodedParser::from_config("enemy = A:Rock\nyou = X:Rock\noutcome = Y:Win")
+            .unwrap_err();
+        assert_eq!(3, err.position().line);
+    }
+}
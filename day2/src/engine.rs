@@ -0,0 +1,309 @@
+use std::{fmt, sync::OnceLock};
+
+use crate::{BattleResult, HandGesture, Scorer};
+
+/// Index of a gesture in the [`Game`] it belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Gesture(pub usize);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleError {
+    NoGestures,
+    DuplicateGesture(String),
+    UnknownGesture(String),
+    BeatsItself(String),
+    /// Both gestures are declared to beat each other.
+    Contradiction(String, String),
+    /// Neither gesture beats the other.
+    Undecided(String, String),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::NoGestures => write!(f, "a game needs at least one gesture"),
+            RuleError::DuplicateGesture(name) => write!(f, "gesture `{}` is declared twice", name),
+            RuleError::UnknownGesture(name) => write!(f, "unknown gesture `{}`", name),
+            RuleError::BeatsItself(name) => write!(f, "`{}` cannot beat itself", name),
+            RuleError::Contradiction(a, b) => {
+                write!(
+                    f,
+                    "`{}` and `{}` are both declared to beat each other",
+                    a, b
+                )
+            }
+            RuleError::Undecided(a, b) => write!(f, "no rule decides `{}` against `{}`", a, b),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// A hand game described by its gestures, their scores and which gesture beats which.
+/// Every pair of distinct gestures has exactly one winner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Game {
+    names: Vec<String>,
+    scores: Vec<u32>,
+    /// `beats[a][b]` is true when gesture `a` beats gesture `b`.
+    beats: Vec<Vec<bool>>,
+}
+
+impl Game {
+    /// Builds a game from `(name, score)` gestures and `(winner, loser)` rules.
+    pub fn new(gestures: &[(&str, u32)], rules: &[(&str, &str)]) -> Result<Game, RuleError> {
+        if gestures.is_empty() {
+            return Err(RuleError::NoGestures);
+        }
+        let names: Vec<String> = gestures.iter().map(|(name, _)| name.to_string()).collect();
+        if let Some(duplicate) = names
+            .iter()
+            .enumerate()
+            .find(|(i, name)| names[..*i].contains(name))
+        {
+            return Err(RuleError::DuplicateGesture(duplicate.1.clone()));
+        }
+
+        let index = |name: &str| {
+            names
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| RuleError::UnknownGesture(name.to_string()))
+        };
+        let mut beats = vec![vec![false; names.len()]; names.len()];
+        for (winner, loser) in rules {
+            let (w, l) = (index(winner)?, index(loser)?);
+            if w == l {
+                return Err(RuleError::BeatsItself(names[w].clone()));
+            }
+            if beats[l][w] {
+                return Err(RuleError::Contradiction(names[w].clone(), names[l].clone()));
+            }
+            beats[w][l] = true;
+        }
+
+        for a in 0..names.len() {
+            for b in a + 1..names.len() {
+                if !beats[a][b] && !beats[b][a] {
+                    return Err(RuleError::Undecided(names[a].clone(), names[b].clone()));
+                }
+            }
+        }
+
+        Ok(Game {
+            names,
+            scores: gestures.iter().map(|(_, score)| *score).collect(),
+            beats,
+        })
+    }
+
+    /// Balanced tournament of an odd number of gestures: each beats the `(n - 1) / 2`
+    /// gestures listed right before it, wrapping around. Gestures score `1..=n` in order.
+    pub fn cyclic(names: &[&str]) -> Result<Game, RuleError> {
+        let n = names.len();
+        let gestures: Vec<(&str, u32)> = names.iter().zip(1..).map(|(n, s)| (*n, s)).collect();
+        let rules: Vec<(&str, &str)> = (0..n)
+            .flat_map(|i| (1..=(n.saturating_sub(1)) / 2).map(move |d| (i, (i + n - d) % n)))
+            .map(|(winner, loser)| (names[winner], names[loser]))
+            .collect();
+
+        Game::new(&gestures, &rules)
+    }
+
+    pub fn rock_paper_scissors() -> Game {
+        Game::cyclic(&["Rock", "Paper", "Scissors"]).expect("valid rules")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Game {
+        Game::new(
+            &[
+                ("Rock", 1),
+                ("Paper", 2),
+                ("Scissors", 3),
+                ("Lizard", 4),
+                ("Spock", 5),
+            ],
+            &[
+                ("Scissors", "Paper"),
+                ("Paper", "Rock"),
+                ("Rock", "Lizard"),
+                ("Lizard", "Spock"),
+                ("Spock", "Scissors"),
+                ("Scissors", "Lizard"),
+                ("Lizard", "Paper"),
+                ("Paper", "Spock"),
+                ("Spock", "Rock"),
+                ("Rock", "Scissors"),
+            ],
+        )
+        .expect("valid rules")
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn gestures(&self) -> impl Iterator<Item = Gesture> {
+        (0..self.len()).map(Gesture)
+    }
+
+    pub fn gesture(&self, name: &str) -> Option<Gesture> {
+        self.names.iter().position(|n| n == name).map(Gesture)
+    }
+
+    pub fn name(&self, gesture: Gesture) -> &str {
+        &self.names[gesture.0]
+    }
+
+    pub fn score(&self, gesture: Gesture) -> u32 {
+        self.scores[gesture.0]
+    }
+
+    pub fn beats(&self, a: Gesture, b: Gesture) -> bool {
+        self.beats[a.0][b.0]
+    }
+
+    /// Outcome of the round for `you`.
+    pub fn battle(&self, enemy: Gesture, you: Gesture) -> BattleResult {
+        if self.beats(you, enemy) {
+            BattleResult::Win
+        } else if self.beats(enemy, you) {
+            BattleResult::Lose
+        } else {
+            BattleResult::Draw
+        }
+    }
+
+    /// Every gesture giving `result` against `enemy`, in declaration order.
+    pub fn responses(&self, result: BattleResult, enemy: Gesture) -> Vec<Gesture> {
+        self.gestures()
+            .filter(|you| self.battle(enemy, *you) == result)
+            .collect()
+    }
+
+    /// First gesture giving `result` against `enemy`, if any does.
+    pub fn when_enemy(&self, result: BattleResult, enemy: Gesture) -> Option<Gesture> {
+        self.gestures()
+            .find(|you| self.battle(enemy, *you) == result)
+    }
+
+    /// Gesture score plus outcome score of one round, from the point of view of `you`.
+    pub fn score_round(&self, enemy: Gesture, you: Gesture) -> u32 {
+        self.score(you) + self.battle(enemy, you).score()
+    }
+}
+
+/// Rock, paper, scissors as played by [`HandGesture`].
+pub fn classic() -> &'static Game {
+    static CLASSIC: OnceLock<Game> = OnceLock::new();
+    CLASSIC.get_or_init(Game::rock_paper_scissors)
+}
+
+impl From<HandGesture> for Gesture {
+    fn from(gesture: HandGesture) -> Self {
+        match gesture {
+            HandGesture::Rock => Gesture(0),
+            HandGesture::Paper => Gesture(1),
+            HandGesture::Scissors => Gesture(2),
+        }
+    }
+}
+
+impl From<Gesture> for HandGesture {
+    fn from(gesture: Gesture) -> Self {
+        match gesture {
+            Gesture(0) => HandGesture::Rock,
+            Gesture(1) => HandGesture::Paper,
+            Gesture(2) => HandGesture::Scissors,
+            Gesture(other) => panic!("rock, paper, scissors has no gesture {}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_table_matches_hand_gestures() {
+        let game = classic();
+        assert_eq!(Some(Gesture(1)), game.gesture("Paper"));
+        assert!(game.beats(
+            Gesture::from(HandGesture::Paper),
+            Gesture::from(HandGesture::Rock)
+        ));
+        assert_eq!(
+            vec![1, 2, 3],
+            game.gestures().map(|g| game.score(g)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rpsls_rules() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let g = |name| game.gesture(name).unwrap();
+
+        assert_eq!(BattleResult::Win, game.battle(g("Lizard"), g("Rock")));
+        assert_eq!(BattleResult::Lose, game.battle(g("Spock"), g("Scissors")));
+        assert_eq!(
+            vec![g("Paper"), g("Lizard")],
+            game.responses(BattleResult::Win, g("Spock"))
+        );
+        assert_eq!(
+            Some(g("Spock")),
+            game.when_enemy(BattleResult::Draw, g("Spock"))
+        );
+        assert_eq!(5 + 6, game.score_round(g("Rock"), g("Spock")));
+    }
+
+    #[test]
+    fn cyclic_tournament_is_balanced() {
+        for n in [1, 3, 5, 7, 9] {
+            let names: Vec<String> = (0..n).map(|i| format!("g{}", i)).collect();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            let game = Game::cyclic(&names).unwrap();
+
+            for g in game.gestures() {
+                let wins = game
+                    .gestures()
+                    .filter(|other| game.beats(g, *other))
+                    .count();
+                assert_eq!((n - 1) / 2, wins);
+            }
+        }
+        assert_eq!(
+            Err(RuleError::Undecided("a".to_string(), "c".to_string())),
+            Game::cyclic(&["a", "b", "c", "d"])
+        );
+    }
+
+    #[test]
+    fn inconsistent_rules_are_rejected() {
+        let gestures = [("a", 1), ("b", 2)];
+        assert_eq!(Err(RuleError::NoGestures), Game::new(&[], &[]));
+        assert_eq!(
+            Err(RuleError::DuplicateGesture("a".to_string())),
+            Game::new(&[("a", 1), ("a", 2)], &[])
+        );
+        assert_eq!(
+            Err(RuleError::UnknownGesture("c".to_string())),
+            Game::new(&gestures, &[("a", "c")])
+        );
+        assert_eq!(
+            Err(RuleError::BeatsItself("a".to_string())),
+            Game::new(&gestures, &[("a", "a")])
+        );
+        assert_eq!(
+            Err(RuleError::Contradiction("b".to_string(), "a".to_string())),
+            Game::new(&gestures, &[("a", "b"), ("b", "a")])
+        );
+        assert_eq!(
+            Err(RuleError::Undecided("a".to_string(), "b".to_string())),
+            Game::new(&gestures, &[])
+        );
+    }
+}
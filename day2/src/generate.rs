@@ -0,0 +1,26 @@
+use aoc_core::Rng;
+
+/// Strategy guide of `rounds` lines like `A Y`.
+pub fn generate(rng: &mut Rng, rounds: usize) -> String {
+    (0..rounds)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_battle_log, Part1Parser};
+
+    #[test]
+    fn generated_guide_parses() {
+        let guide = generate(&mut Rng::new(1), 100);
+        assert_eq!(100, parse_battle_log(&guide, &Part1Parser).unwrap().len());
+    }
+}
@@ -0,0 +1,162 @@
+use std::{cmp::Reverse, fmt};
+
+use aoc_core::ParseError;
+
+use crate::{calculate_result, split_log, BattleResult, HandGesture, LogParser, Round};
+
+const CODES: [&str; 3] = ["X", "Y", "Z"];
+
+/// One possible meaning of the X/Y/Z column.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Interpretation {
+    Gestures([HandGesture; 3]),
+    Outcomes([BattleResult; 3]),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub interpretation: Interpretation,
+    pub total: u32,
+}
+
+impl Interpretation {
+    /// All 12 readings: every gesture permutation and every outcome permutation.
+    pub fn all() -> Vec<Interpretation> {
+        let gestures = [HandGesture::Rock, HandGesture::Paper, HandGesture::Scissors];
+        let outcomes = [BattleResult::Lose, BattleResult::Draw, BattleResult::Win];
+
+        let mut interpretations: Vec<Interpretation> = permutations()
+            .iter()
+            .map(|order| Interpretation::Gestures(order.map(|i| gestures[i].clone())))
+            .collect();
+        interpretations.extend(
+            permutations()
+                .iter()
+                .map(|order| Interpretation::Outcomes(order.map(|i| outcomes[i]))),
+        );
+        interpretations
+    }
+}
+
+fn permutations() -> [[usize; 3]; 6] {
+    [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ]
+}
+
+impl LogParser for Interpretation {
+    fn parse_log(&self, log: &str) -> Result<Round, ParseError> {
+        let (enemy, code) = split_log(log)?;
+        let enemy = HandGesture::from(enemy).map_err(|err| err.within(log, enemy))?;
+        let index = CODES
+            .iter()
+            .position(|c| *c == code)
+            .ok_or_else(|| ParseError::unexpected(code, "one of X, Y, Z").within(log, code))?;
+        let you = match self {
+            Interpretation::Gestures(gestures) => gestures[index].clone(),
+            Interpretation::Outcomes(outcomes) => outcomes[index].when_enemy(enemy.clone()),
+        };
+        Ok((enemy, you))
+    }
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meanings: Vec<String> = match self {
+            Interpretation::Gestures(gestures) => {
+                gestures.iter().map(|g| format!("{:?}", g)).collect()
+            }
+            Interpretation::Outcomes(outcomes) => {
+                outcomes.iter().map(|o| format!("{:?}", o)).collect()
+            }
+        };
+        let pairs: Vec<String> = CODES
+            .iter()
+            .zip(meanings)
+            .map(|(code, meaning)| format!("{}={}", code, meaning))
+            .collect();
+        write!(f, "{}", pairs.join(" "))
+    }
+}
+
+/// Scores the guide under every interpretation, highest total first.
+pub fn rank(guide: &str) -> Result<Vec<Candidate>, ParseError> {
+    let mut candidates = Interpretation::all()
+        .into_iter()
+        .map(|interpretation| {
+            let total = calculate_result(guide, Box::new(interpretation.clone()))?;
+            Ok(Candidate {
+                interpretation,
+                total,
+            })
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    candidates.sort_by_key(|candidate| Reverse(candidate.total));
+    Ok(candidates)
+}
+
+/// Interpretations under which the guide scores exactly `observed`.
+pub fn infer(guide: &str, observed: u32) -> Result<Vec<Interpretation>, ParseError> {
+    Ok(rank(guide)?
+        .into_iter()
+        .filter(|candidate| candidate.total == observed)
+        .map(|candidate| candidate.interpretation)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "A Y\nB X\nC Z";
+
+    #[test]
+    fn twelve_distinct_interpretations() {
+        let all = Interpretation::all();
+        assert_eq!(12, all.len());
+        assert!(all
+            .iter()
+            .enumerate()
+            .all(|(i, a)| !all[i + 1..].contains(a)));
+    }
+
+    #[test]
+    fn sample_scores_point_back_to_the_puzzle_readings() {
+        use BattleResult::*;
+        use HandGesture::*;
+
+        assert!(infer(SAMPLE, 15)
+            .unwrap()
+            .contains(&Interpretation::Gestures([Rock, Paper, Scissors])));
+        assert!(infer(SAMPLE, 12)
+            .unwrap()
+            .contains(&Interpretation::Outcomes([Lose, Draw, Win])));
+        assert_eq!(Ok(vec![]), infer(SAMPLE, 1000));
+    }
+
+    #[test]
+    fn ranking_is_descending_and_covers_everything() {
+        let ranked = rank(include_str!("../input.txt")).unwrap();
+        assert_eq!(12, ranked.len());
+        assert!(ranked.windows(2).all(|w| w[0].total >= w[1].total));
+        assert!(ranked.iter().any(|c| c.total == 12156));
+        assert!(ranked.iter().any(|c| c.total == 10835));
+    }
+
+    #[test]
+    fn display_lists_each_code() {
+        let reading =
+            Interpretation::Outcomes([BattleResult::Lose, BattleResult::Draw, BattleResult::Win]);
+        assert_eq!("X=Lose Y=Draw Z=Win", reading.to_string());
+    }
+
+    #[test]
+    fn invalid_guides_are_reported() {
+        assert_eq!(1, rank("A Q").unwrap_err().position().line);
+    }
+}
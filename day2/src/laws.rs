@@ -0,0 +1,165 @@
+use crate::{
+    engine::{Game, Gesture},
+    BattleResult,
+};
+
+const RESULTS: [BattleResult; 3] = [BattleResult::Win, BattleResult::Draw, BattleResult::Lose];
+
+/// A law of the rule table that does not hold, naming the gestures involved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// `battle(enemy, when_enemy(result, enemy))` is not `result`.
+    WhenEnemy { enemy: String, result: BattleResult },
+    /// `when_enemy` finds nothing although `responses` lists a gesture, or the other way round.
+    Unreachable { enemy: String, result: BattleResult },
+    /// Swapping the players does not swap win and loss.
+    NotAntisymmetric { enemy: String, you: String },
+    /// A gesture does not draw against itself.
+    NoSelfDraw(String),
+    /// Two distinct gestures draw, so the round is undecided.
+    Undecided { enemy: String, you: String },
+}
+
+fn flipped(result: BattleResult) -> BattleResult {
+    match result {
+        BattleResult::Win => BattleResult::Lose,
+        BattleResult::Draw => BattleResult::Draw,
+        BattleResult::Lose => BattleResult::Win,
+    }
+}
+
+/// Checks every law on every gesture and outcome of `game`.
+pub fn check(game: &Game) -> Vec<Violation> {
+    let name = |gesture: Gesture| game.name(gesture).to_string();
+    let mut violations = Vec::new();
+
+    for enemy in game.gestures() {
+        for you in game.gestures() {
+            let result = game.battle(enemy, you);
+            if enemy == you && result != BattleResult::Draw {
+                violations.push(Violation::NoSelfDraw(name(enemy)));
+            }
+            if enemy != you && result == BattleResult::Draw {
+                violations.push(Violation::Undecided {
+                    enemy: name(enemy),
+                    you: name(you),
+                });
+            }
+            if game.battle(you, enemy) != flipped(result) {
+                violations.push(Violation::NotAntisymmetric {
+                    enemy: name(enemy),
+                    you: name(you),
+                });
+            }
+        }
+
+        for result in RESULTS {
+            match game.when_enemy(result, enemy) {
+                Some(you) if game.battle(enemy, you) != result => {
+                    violations.push(Violation::WhenEnemy {
+                        enemy: name(enemy),
+                        result,
+                    })
+                }
+                found if found.is_none() != game.responses(result, enemy).is_empty() => violations
+                    .push(Violation::Unreachable {
+                        enemy: name(enemy),
+                        result,
+                    }),
+                _ => {}
+            }
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Rng;
+
+    use super::*;
+    use crate::{
+        battle, calculate_result, generate::generate, HandGesture, LogParser, Part1Parser,
+        Part2Parser,
+    };
+
+    /// Random rule table: every pair of gestures gets a random winner.
+    fn random_game(rng: &mut Rng, size: usize) -> Game {
+        let names: Vec<String> = (0..size).map(|i| format!("g{}", i)).collect();
+        let gestures: Vec<(&str, u32)> = names
+            .iter()
+            .map(|name| (name.as_str(), rng.range(0..=9) as u32))
+            .collect();
+        let mut rules = Vec::new();
+        for a in 0..size {
+            for b in a + 1..size {
+                let (winner, loser) = if rng.below(2) == 0 { (a, b) } else { (b, a) };
+                rules.push((names[winner].as_str(), names[loser].as_str()));
+            }
+        }
+        Game::new(&gestures, &rules).unwrap()
+    }
+
+    #[test]
+    fn bundled_games_obey_every_law() {
+        assert_eq!(Vec::<Violation>::new(), check(crate::engine::classic()));
+        assert_eq!(
+            Vec::<Violation>::new(),
+            check(&Game::rock_paper_scissors_lizard_spock())
+        );
+        for size in [1, 3, 5, 7, 9] {
+            let names: Vec<String> = (0..size).map(|i| i.to_string()).collect();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            assert_eq!(
+                Vec::<Violation>::new(),
+                check(&Game::cyclic(&names).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn random_rule_tables_obey_every_law() {
+        let mut rng = Rng::new(21);
+        for _ in 0..200 {
+            let size = rng.range(1..=8);
+            let game = random_game(&mut rng, size);
+            assert_eq!(Vec::<Violation>::new(), check(&game), "{:?}", game);
+        }
+    }
+
+    #[test]
+    fn hand_gestures_round_trip_every_outcome() {
+        for enemy in [HandGesture::Rock, HandGesture::Paper, HandGesture::Scissors] {
+            for result in RESULTS {
+                let you = result.when_enemy(enemy.clone());
+                assert_eq!(result, battle(enemy.clone(), you.clone()));
+                assert_eq!(flipped(result), battle(you, enemy.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn parsers_agree_when_the_encodings_coincide() {
+        let mut rng = Rng::new(2);
+        for size in [0, 1, 10, 500] {
+            let guide = generate(&mut rng, size);
+            // Rewrite each gesture as the outcome it produces, so part 2 must play the same moves.
+            let outcomes: String = guide
+                .lines()
+                .map(|line| {
+                    let (enemy, you) = Part1Parser.parse_log(line).unwrap();
+                    let code = match battle(enemy, you) {
+                        BattleResult::Lose => "X",
+                        BattleResult::Draw => "Y",
+                        BattleResult::Win => "Z",
+                    };
+                    format!("{} {}\n", &line[..1], code)
+                })
+                .collect();
+            assert_eq!(
+                calculate_result(&guide, Box::new(Part1Parser)),
+                calculate_result(&outcomes, Box::new(Part2Parser))
+            );
+        }
+    }
+}
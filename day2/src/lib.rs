@@ -0,0 +1,452 @@
+pub mod audit;
+pub mod encoding;
+pub mod engine;
+pub mod generate;
+pub mod infer;
+pub mod laws;
+pub mod repl;
+pub mod synthesis;
+pub mod tournament;
+
+use aoc_core::{ParseError, Solution};
+use engine::{classic, Gesture};
+
+pub struct Day2;
+
+pub type Round = (HandGesture, HandGesture);
+
+pub struct StrategyGuide {
+    pub rounds_part_1: Vec<Round>,
+    pub rounds_part_2: Vec<Round>,
+}
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    type Input = StrategyGuide;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(StrategyGuide {
+            rounds_part_1: parse_battle_log(input, &Part1Parser)?,
+            rounds_part_2: parse_battle_log(input, &Part2Parser)?,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        score_rounds(&input.rounds_part_1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        score_rounds(&input.rounds_part_2)
+    }
+}
+
+pub fn calculate_result(battle_log: &str, parser: Box<dyn LogParser>) -> Result<u32, ParseError> {
+    score_log(battle_log, parser.as_ref())
+}
+
+/// Parses and scores the log line by line without collecting the rounds.
+pub fn score_log<P: LogParser + ?Sized>(battle_log: &str, parser: &P) -> Result<u32, ParseError> {
+    battle_log
+        .lines()
+        .enumerate()
+        .try_fold(0, |total, (index, log)| {
+            let (enemy, you) = parser
+                .parse_log(log)
+                .map_err(|err| err.on_line(index + 1))?;
+            Ok(total + score_round(&enemy, &you))
+        })
+}
+
+pub fn parse_battle_log(
+    battle_log: &str,
+    parser: &dyn LogParser,
+) -> Result<Vec<Round>, ParseError> {
+    battle_log
+        .lines()
+        .enumerate()
+        .map(|(index, log)| parser.parse_log(log).map_err(|err| err.on_line(index + 1)))
+        .collect()
+}
+
+pub fn score_rounds(rounds: &[Round]) -> u32 {
+    rounds
+        .iter()
+        .map(|(enemy, you)| score_round(enemy, you))
+        .sum()
+}
+
+pub fn score_round(enemy: &HandGesture, you: &HandGesture) -> u32 {
+    battle(enemy.clone(), you.clone()).score() + you.score()
+}
+
+/// Scores of the nine canonical lines `A X` to `C Z` under one parser.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoreTable {
+    /// `None` where the parser rejects the line.
+    scores: [[Option<u32>; 3]; 3],
+}
+
+impl ScoreTable {
+    pub fn new<P: LogParser + ?Sized>(parser: &P) -> ScoreTable {
+        let mut scores = [[None; 3]; 3];
+        for (enemy, row) in ["A", "B", "C"].iter().zip(&mut scores) {
+            for (you, score) in ["X", "Y", "Z"].iter().zip(row) {
+                *score = parser
+                    .parse_log(&format!("{} {}", enemy, you))
+                    .ok()
+                    .map(|(enemy, you)| score_round(&enemy, &you));
+            }
+        }
+        ScoreTable { scores }
+    }
+
+    fn lookup(&self, log: &str) -> Option<u32> {
+        match *log.as_bytes() {
+            [enemy @ b'A'..=b'C', b' ', you @ b'X'..=b'Z'] => {
+                self.scores[usize::from(enemy - b'A')][usize::from(you - b'X')]
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Same total as [`score_log`], looking canonical lines up in `table` and only
+/// handing the rest to `parser`.
+pub fn score_log_with_table<P: LogParser + ?Sized>(
+    battle_log: &str,
+    table: &ScoreTable,
+    parser: &P,
+) -> Result<u32, ParseError> {
+    battle_log
+        .lines()
+        .enumerate()
+        .try_fold(0, |total, (index, log)| {
+            let score = match table.lookup(log) {
+                Some(score) => score,
+                None => {
+                    let (enemy, you) = parser
+                        .parse_log(log)
+                        .map_err(|err| err.on_line(index + 1))?;
+                    score_round(&enemy, &you)
+                }
+            };
+            Ok(total + score)
+        })
+}
+
+pub trait LogParser {
+    fn parse_log(&self, log: &str) -> Result<Round, ParseError>;
+}
+
+pub struct Part1Parser;
+
+impl LogParser for Part1Parser {
+    fn parse_log(&self, log: &str) -> Result<Round, ParseError> {
+        let (enemy, you) = split_log(log)?;
+        Ok((
+            HandGesture::from(enemy).map_err(|err| err.within(log, enemy))?,
+            HandGesture::from(you).map_err(|err| err.within(log, you))?,
+        ))
+    }
+}
+
+pub struct Part2Parser;
+
+impl LogParser for Part2Parser {
+    fn parse_log(&self, log: &str) -> Result<Round, ParseError> {
+        let (enemy, result) = split_log(log)?;
+        let enemy = HandGesture::from(enemy).map_err(|err| err.within(log, enemy))?;
+        let you = BattleResult::from(result)
+            .map_err(|err| err.within(log, result))?
+            .when_enemy(enemy.clone());
+        Ok((enemy, you))
+    }
+}
+
+fn split_log(log: &str) -> Result<(&str, &str), ParseError> {
+    let mut splitted = log.split(' ');
+    let enemy = splitted
+        .next()
+        .filter(|enemy| !enemy.is_empty())
+        .ok_or_else(|| ParseError::missing(log, "an enemy gesture"))?;
+    let you = splitted
+        .next()
+        .ok_or_else(|| ParseError::missing(log, "a second column"))?;
+    if let Some(extra) = splitted.next() {
+        return Err(ParseError::unexpected(extra, "end of line").within(log, extra));
+    }
+    Ok((enemy, you))
+}
+
+pub trait Scorer {
+    fn score(&self) -> u32;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HandGesture {
+    Rock,
+    Scissors,
+    Paper,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BattleResult {
+    Win,
+    Draw,
+    Lose,
+}
+
+impl BattleResult {
+    pub fn from(c: &str) -> Result<BattleResult, ParseError> {
+        match c {
+            "X" => Ok(BattleResult::Lose),
+            "Y" => Ok(BattleResult::Draw),
+            "Z" => Ok(BattleResult::Win),
+            _ => Err(ParseError::unexpected(c, "one of X, Y, Z")),
+        }
+    }
+
+    pub fn when_enemy(&self, enemy: HandGesture) -> HandGesture {
+        classic()
+            .when_enemy(*self, Gesture::from(enemy))
+            .expect("every outcome is reachable in rock, paper, scissors")
+            .into()
+    }
+}
+
+impl HandGesture {
+    pub fn from(c: &str) -> Result<HandGesture, ParseError> {
+        match c {
+            "A" | "X" => Ok(HandGesture::Rock),
+            "B" | "Y" => Ok(HandGesture::Paper),
+            "C" | "Z" => Ok(HandGesture::Scissors),
+            _ => Err(ParseError::unexpected(c, "one of A, B, C, X, Y, Z")),
+        }
+    }
+}
+
+impl Scorer for HandGesture {
+    fn score(&self) -> u32 {
+        classic().score(Gesture::from(self.clone()))
+    }
+}
+
+impl Scorer for BattleResult {
+    fn score(&self) -> u32 {
+        match self {
+            BattleResult::Lose => 0,
+            BattleResult::Draw => 3,
+            BattleResult::Win => 6,
+        }
+    }
+}
+
+pub fn battle(enemy: HandGesture, you: HandGesture) -> BattleResult {
+    classic().battle(enemy.into(), you.into())
+}
+
+pub fn score_collector(scores: Vec<Box<dyn Scorer>>) -> u32 {
+    scores.iter().map(|x| x.score()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn rock_defeats_scissors() {
+        assert_eq!(
+            BattleResult::Win,
+            battle(HandGesture::Scissors, HandGesture::Rock)
+        );
+
+        assert_eq!(
+            BattleResult::Lose,
+            battle(HandGesture::Rock, HandGesture::Scissors)
+        );
+
+        assert_eq!(
+            BattleResult::Draw,
+            battle(HandGesture::Rock, HandGesture::Rock)
+        );
+    }
+
+    #[test]
+    fn scissors_defeats_paper() {
+        assert_eq!(
+            BattleResult::Win,
+            battle(HandGesture::Paper, HandGesture::Scissors)
+        );
+
+        assert_eq!(
+            BattleResult::Lose,
+            battle(HandGesture::Scissors, HandGesture::Paper)
+        );
+
+        assert_eq!(
+            BattleResult::Draw,
+            battle(HandGesture::Scissors, HandGesture::Scissors)
+        );
+    }
+
+    #[test]
+    fn paper_defeats_rock() {
+        assert_eq!(
+            BattleResult::Win,
+            battle(HandGesture::Rock, HandGesture::Paper)
+        );
+
+        assert_eq!(
+            BattleResult::Lose,
+            battle(HandGesture::Paper, HandGesture::Rock)
+        );
+
+        assert_eq!(
+            BattleResult::Draw,
+            battle(HandGesture::Paper, HandGesture::Paper)
+        );
+    }
+
+    #[test]
+    fn sum_all_scores() {
+        assert_eq!(1, score_collector(vec![Box::new(HandGesture::Rock)]));
+        assert_eq!(2, score_collector(vec![Box::new(HandGesture::Paper)]));
+        assert_eq!(3, score_collector(vec![Box::new(HandGesture::Scissors)]));
+
+        assert_eq!(6, score_collector(vec![Box::new(BattleResult::Win)]));
+        assert_eq!(3, score_collector(vec![Box::new(BattleResult::Draw)]));
+        assert_eq!(0, score_collector(vec![Box::new(BattleResult::Lose)]));
+
+        assert_eq!(
+            15,
+            score_collector(vec![
+                Box::new(HandGesture::Rock),
+                Box::new(HandGesture::Paper),
+                Box::new(HandGesture::Scissors),
+                Box::new(BattleResult::Win),
+                Box::new(BattleResult::Lose),
+                Box::new(BattleResult::Draw),
+            ])
+        )
+    }
+
+    #[test]
+    fn part1_should_be_15() {
+        const DATA: &str = "A Y\nB X\nC Z";
+        assert_eq!(Ok(15), calculate_result(DATA, Box::new(Part1Parser)));
+    }
+
+    // part 2
+
+    #[test]
+    fn calculate_hand_gesture_when_enemy_choose_rock() {
+        assert_eq!(
+            HandGesture::Paper,
+            BattleResult::Win.when_enemy(HandGesture::Rock)
+        );
+
+        assert_eq!(
+            HandGesture::Rock,
+            BattleResult::Draw.when_enemy(HandGesture::Rock)
+        );
+
+        assert_eq!(
+            HandGesture::Scissors,
+            BattleResult::Lose.when_enemy(HandGesture::Rock)
+        );
+    }
+
+    #[test]
+    fn calculate_hand_gesture_when_enemy_choose_paper() {
+        assert_eq!(
+            HandGesture::Scissors,
+            BattleResult::Win.when_enemy(HandGesture::Paper)
+        );
+
+        assert_eq!(
+            HandGesture::Paper,
+            BattleResult::Draw.when_enemy(HandGesture::Paper)
+        );
+
+        assert_eq!(
+            HandGesture::Rock,
+            BattleResult::Lose.when_enemy(HandGesture::Paper)
+        );
+    }
+
+    #[test]
+    fn calculate_hand_gesture_when_enemy_choose_scissors() {
+        assert_eq!(
+            HandGesture::Rock,
+            BattleResult::Win.when_enemy(HandGesture::Scissors)
+        );
+
+        assert_eq!(
+            HandGesture::Scissors,
+            BattleResult::Draw.when_enemy(HandGesture::Scissors)
+        );
+
+        assert_eq!(
+            HandGesture::Paper,
+            BattleResult::Lose.when_enemy(HandGesture::Scissors)
+        );
+    }
+
+    #[test]
+    fn part_2_should_be_12() {
+        const DATA: &str = "A Y\nB X\nC Z";
+        assert_eq!(Ok(12), calculate_result(DATA, Box::new(Part2Parser)));
+    }
+
+    #[test]
+    fn unknown_gesture_is_reported_with_position() {
+        const DATA: &str = "A Y\nB Q\nC Z";
+        let err = calculate_result(DATA, Box::new(Part2Parser)).unwrap_err();
+        assert_eq!(Position { line: 2, column: 3 }, err.position());
+        assert_eq!("Q", err.token());
+    }
+
+    #[test]
+    fn missing_column_is_reported() {
+        let err = Part1Parser.parse_log("A").unwrap_err();
+        assert_eq!(Position { line: 1, column: 2 }, err.position());
+    }
+
+    #[test]
+    fn every_scoring_path_agrees() {
+        let log = generate::generate(&mut aoc_core::Rng::new(4), 10_000);
+        let custom = encoding::EncodedParser::from_config(
+            "enemy = A:Scissors B:Rock C:Paper\noutcome = X:Win Y:Lose Z:Draw",
+        )
+        .unwrap();
+        let parsers: [&dyn LogParser; 3] = [&Part1Parser, &Part2Parser, &custom];
+        for parser in parsers {
+            let expected = score_rounds(&parse_battle_log(&log, parser).unwrap());
+            assert_eq!(Ok(expected), score_log(&log, parser));
+            assert_eq!(
+                Ok(expected),
+                score_log_with_table(&log, &ScoreTable::new(parser), parser)
+            );
+        }
+    }
+
+    #[test]
+    fn table_falls_back_to_the_parser() {
+        let table = ScoreTable::new(&Part1Parser);
+        // `X Y` is outside the table but still valid for part 1.
+        assert_eq!(
+            Ok(8 + 8),
+            score_log_with_table("A Y\nX Y", &table, &Part1Parser)
+        );
+
+        let err = score_log_with_table("A Y\nA Y\nD X", &table, &Part1Parser).unwrap_err();
+        assert_eq!(Position { line: 3, column: 1 }, err.position());
+        assert_eq!(Err(err), score_log("A Y\nA Y\nD X", &Part1Parser));
+    }
+}
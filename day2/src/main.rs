@@ -1,302 +1,141 @@
-use std::fs;
-
-fn main() {
-    let battle_log = fs::read_to_string("input.txt").unwrap();
-
-    let result_part_1 = calculate_result(&battle_log, Box::new(Part1Parser));
-    println!("{}", result_part_1);
-
-    let result_part_2 = calculate_result(&battle_log, Box::new(Part2Parser));
-    println!("{}", result_part_2);
+use std::{env, fs, io, process};
+
+use aoc_core::ParseError;
+use day2::{
+    audit::Audit,
+    encoding::EncodedParser,
+    infer, repl, score_log_with_table,
+    tournament::{self, Constant, Frequency, Markov, Replay, Strategy, Uniform},
+    HandGesture, LogParser, Part1Parser, Part2Parser, ScoreTable,
+};
+
+const BOTS: [&str; 5] = ["rock", "random", "frequency", "markov", "replay"];
+
+const USAGE: &str = "usage: day2 [<encoding.cfg>]
+       day2 --infer <total>
+       day2 --audit <1|2>
+       day2 --tournament <rounds>
+       day2 --play <rock|random|frequency|markov|replay> [<session.txt>]";
+
+fn usage_error(message: String) -> ! {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    process::exit(1)
 }
 
-fn calculate_result(battle_log: &str, parser: Box<dyn LogParser>) -> u32 {
-    battle_log
-        .lines()
-        .map(|log| parser.parse_log(log))
-        .map(|(enemy, you)| {
-            score_collector(vec![Box::new(battle(enemy, you.clone())), Box::new(you)])
-        })
-        .sum()
+fn parse_value<T: std::str::FromStr>(flag: &str, value: &str) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| usage_error(format!("invalid value `{}` for `{}`", value, flag)))
 }
 
-trait LogParser {
-    fn parse_log(&self, log: &str) -> (HandGesture, HandGesture);
-}
-
-struct Part1Parser;
-
-impl LogParser for Part1Parser {
-    fn parse_log(&self, log: &str) -> (HandGesture, HandGesture) {
-        let mut splitted = log.split(' ');
-        (
-            HandGesture::from(splitted.next().unwrap()),
-            HandGesture::from(splitted.next().unwrap()),
-        )
-    }
+fn exit_on_error(origin: &str, source: &str, err: ParseError) -> ! {
+    eprintln!("{}", err.render(origin, source));
+    process::exit(1)
 }
 
-struct Part2Parser;
-
-impl LogParser for Part2Parser {
-    fn parse_log(&self, log: &str) -> (HandGesture, HandGesture) {
-        let mut splitted = log.split(' ');
-        let enemy = HandGesture::from(splitted.next().unwrap());
-        let you = BattleResult::from(splitted.next().unwrap()).when_enemy(enemy.clone());
-        (enemy, you)
+fn bot(name: &str, battle_log: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "rock" => Some(Box::new(Constant(HandGesture::Rock))),
+        "random" => Some(Box::new(Uniform::new(0))),
+        "frequency" => Some(Box::new(Frequency::default())),
+        "markov" => Some(Box::new(Markov::default())),
+        "replay" => Some(Box::new(
+            Replay::from_guide(battle_log, &Part1Parser)
+                .unwrap_or_else(|err| exit_on_error("input.txt", battle_log, err)),
+        )),
+        _ => None,
     }
 }
 
-trait Scorer {
-    fn score(&self) -> u32;
-}
-
-#[derive(Clone, Debug, PartialEq)]
-enum HandGesture {
-    Rock,
-    Scissors,
-    Paper,
-}
-
-#[derive(Debug, PartialEq)]
-enum BattleResult {
-    Win,
-    Draw,
-    Lose,
-}
-
-impl BattleResult {
-    fn from(c: &str) -> BattleResult {
-        match c {
-            "X" => BattleResult::Lose,
-            "Y" => BattleResult::Draw,
-            "Z" => BattleResult::Win,
-            _ => panic!("not handled"),
+fn main() {
+    let battle_log = fs::read_to_string("input.txt").unwrap();
+    let score = |parser: &dyn LogParser| {
+        score_log_with_table(&battle_log, &ScoreTable::new(parser), parser)
+            .unwrap_or_else(|err| exit_on_error("input.txt", &battle_log, err))
+    };
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        // Lists every X/Y/Z reading, marking the ones that match the total.
+        ["--infer", total] => {
+            let observed: u32 = parse_value("--infer", total);
+            for candidate in infer::rank(&battle_log)
+                .unwrap_or_else(|err| exit_on_error("input.txt", &battle_log, err))
+            {
+                let marker = if candidate.total == observed {
+                    "*"
+                } else {
+                    " "
+                };
+                println!(
+                    "{} {:>6}  {}",
+                    marker, candidate.total, candidate.interpretation
+                );
+            }
         }
-    }
-
-    fn when_enemy(&self, enemy: HandGesture) -> HandGesture {
-        match self {
-            BattleResult::Win => match enemy {
-                HandGesture::Rock => HandGesture::Paper,
-                HandGesture::Paper => HandGesture::Scissors,
-                HandGesture::Scissors => HandGesture::Rock,
-            },
-            BattleResult::Draw => match enemy {
-                HandGesture::Rock => HandGesture::Rock,
-                HandGesture::Paper => HandGesture::Paper,
-                HandGesture::Scissors => HandGesture::Scissors,
-            },
-            BattleResult::Lose => match enemy {
-                HandGesture::Rock => HandGesture::Scissors,
-                HandGesture::Paper => HandGesture::Rock,
-                HandGesture::Scissors => HandGesture::Paper,
-            },
+        // Prints every round as CSV, followed by the summary.
+        ["--audit", part] => {
+            let parser: &dyn LogParser = match part {
+                "1" => &Part1Parser,
+                "2" => &Part2Parser,
+                _ => usage_error(format!("invalid value `{}` for `--audit`", part)),
+            };
+            let audit = Audit::new(&battle_log, parser)
+                .unwrap_or_else(|err| exit_on_error("input.txt", &battle_log, err));
+            print!("{}\n{}", audit.rounds_csv(), audit.summary().to_csv());
         }
-    }
-}
-
-impl HandGesture {
-    fn from(c: &str) -> HandGesture {
-        match c {
-            "A" | "X" => HandGesture::Rock,
-            "B" | "Y" => HandGesture::Paper,
-            "C" | "Z" => HandGesture::Scissors,
-            _ => panic!("not handled"),
+        // Pits the built-in strategies against each other.
+        ["--tournament", rounds] => {
+            let mut entrants: Vec<Box<dyn Strategy>> = BOTS
+                .iter()
+                .filter_map(|name| bot(name, &battle_log))
+                .collect();
+            let rounds: u32 = parse_value("--tournament", rounds);
+            if rounds == 0 {
+                usage_error("`--tournament` expects at least one round".to_string());
+            }
+            print!("{}", tournament::round_robin(&mut entrants, rounds));
         }
-    }
-}
-
-impl Scorer for HandGesture {
-    fn score(&self) -> u32 {
-        match self {
-            HandGesture::Rock => 1,
-            HandGesture::Paper => 2,
-            HandGesture::Scissors => 3,
+        // Plays against a bot on the terminal and saves the session as a strategy guide.
+        ["--play", name, ref save @ ..] if save.len() <= 1 => {
+            let Some(mut bot) = bot(name, &battle_log) else {
+                eprintln!(
+                    "error: unknown bot `{}`, expected one of {}",
+                    name,
+                    BOTS.join(", ")
+                );
+                process::exit(1)
+            };
+            let session = repl::run(io::stdin().lock(), &mut io::stdout(), bot.as_mut())
+                .unwrap_or_else(|err| {
+                    eprintln!("error: cannot play on the terminal: {}", err);
+                    process::exit(1)
+                });
+            let path = save.first().copied().unwrap_or("session.txt");
+            fs::write(path, session.to_log()).unwrap_or_else(|err| {
+                eprintln!("error: cannot write `{}`: {}", path, err);
+                process::exit(1)
+            });
+            println!("saved {} rounds to {}", session.rounds(), path);
         }
-    }
-}
-
-impl Scorer for BattleResult {
-    fn score(&self) -> u32 {
-        match self {
-            BattleResult::Lose => 0,
-            BattleResult::Draw => 3,
-            BattleResult::Win => 6,
+        [flag] if ["--infer", "--audit", "--tournament", "--play"].contains(&flag) => {
+            usage_error(format!("`{}` expects a value", flag))
         }
-    }
-}
-
-fn battle(enemy: HandGesture, you: HandGesture) -> BattleResult {
-    match enemy {
-        HandGesture::Scissors => match you {
-            HandGesture::Rock => BattleResult::Win,
-            HandGesture::Scissors => BattleResult::Draw,
-            HandGesture::Paper => BattleResult::Lose,
-        },
-        HandGesture::Rock => match you {
-            HandGesture::Rock => BattleResult::Draw,
-            HandGesture::Scissors => BattleResult::Lose,
-            HandGesture::Paper => BattleResult::Win,
-        },
-        HandGesture::Paper => match you {
-            HandGesture::Rock => BattleResult::Lose,
-            HandGesture::Scissors => BattleResult::Win,
-            HandGesture::Paper => BattleResult::Draw,
-        },
-    }
-}
-
-fn score_collector(scores: Vec<Box<dyn Scorer>>) -> u32 {
-    scores.iter().map(|x| x.score()).sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn rock_defeats_scissors() {
-        assert_eq!(
-            BattleResult::Win,
-            battle(HandGesture::Scissors, HandGesture::Rock)
-        );
-
-        assert_eq!(
-            BattleResult::Lose,
-            battle(HandGesture::Rock, HandGesture::Scissors)
-        );
-
-        assert_eq!(
-            BattleResult::Draw,
-            battle(HandGesture::Rock, HandGesture::Rock)
-        );
-    }
-
-    #[test]
-    fn scissors_defeats_paper() {
-        assert_eq!(
-            BattleResult::Win,
-            battle(HandGesture::Paper, HandGesture::Scissors)
-        );
-
-        assert_eq!(
-            BattleResult::Lose,
-            battle(HandGesture::Scissors, HandGesture::Paper)
-        );
-
-        assert_eq!(
-            BattleResult::Draw,
-            battle(HandGesture::Scissors, HandGesture::Scissors)
-        );
-    }
-
-    #[test]
-    fn paper_defeats_rock() {
-        assert_eq!(
-            BattleResult::Win,
-            battle(HandGesture::Rock, HandGesture::Paper)
-        );
-
-        assert_eq!(
-            BattleResult::Lose,
-            battle(HandGesture::Paper, HandGesture::Rock)
-        );
-
-        assert_eq!(
-            BattleResult::Draw,
-            battle(HandGesture::Paper, HandGesture::Paper)
-        );
-    }
-
-    #[test]
-    fn sum_all_scores() {
-        assert_eq!(1, score_collector(vec![Box::new(HandGesture::Rock)]));
-        assert_eq!(2, score_collector(vec![Box::new(HandGesture::Paper)]));
-        assert_eq!(3, score_collector(vec![Box::new(HandGesture::Scissors)]));
-
-        assert_eq!(6, score_collector(vec![Box::new(BattleResult::Win)]));
-        assert_eq!(3, score_collector(vec![Box::new(BattleResult::Draw)]));
-        assert_eq!(0, score_collector(vec![Box::new(BattleResult::Lose)]));
-
-        assert_eq!(
-            15,
-            score_collector(vec![
-                Box::new(HandGesture::Rock),
-                Box::new(HandGesture::Paper),
-                Box::new(HandGesture::Scissors),
-                Box::new(BattleResult::Win),
-                Box::new(BattleResult::Lose),
-                Box::new(BattleResult::Draw),
-            ])
-        )
-    }
-
-    #[test]
-    fn part1_should_be_15() {
-        const DATA: &str = "A Y\nB X\nC Z";
-        assert_eq!(15, calculate_result(DATA, Box::new(Part1Parser)));
-    }
-
-    // part 2
-
-    #[test]
-    fn calculate_hand_gesture_when_enemy_choose_rock() {
-        assert_eq!(
-            HandGesture::Paper,
-            BattleResult::Win.when_enemy(HandGesture::Rock)
-        );
-
-        assert_eq!(
-            HandGesture::Rock,
-            BattleResult::Draw.when_enemy(HandGesture::Rock)
-        );
-
-        assert_eq!(
-            HandGesture::Scissors,
-            BattleResult::Lose.when_enemy(HandGesture::Rock)
-        );
-    }
-
-    #[test]
-    fn calculate_hand_gesture_when_enemy_choose_paper() {
-        assert_eq!(
-            HandGesture::Scissors,
-            BattleResult::Win.when_enemy(HandGesture::Paper)
-        );
-
-        assert_eq!(
-            HandGesture::Paper,
-            BattleResult::Draw.when_enemy(HandGesture::Paper)
-        );
-
-        assert_eq!(
-            HandGesture::Rock,
-            BattleResult::Lose.when_enemy(HandGesture::Paper)
-        );
-    }
-
-    #[test]
-    fn calculate_hand_gesture_when_enemy_choose_scissors() {
-        assert_eq!(
-            HandGesture::Rock,
-            BattleResult::Win.when_enemy(HandGesture::Scissors)
-        );
-
-        assert_eq!(
-            HandGesture::Scissors,
-            BattleResult::Draw.when_enemy(HandGesture::Scissors)
-        );
-
-        assert_eq!(
-            HandGesture::Paper,
-            BattleResult::Lose.when_enemy(HandGesture::Scissors)
-        );
-    }
-
-    #[test]
-    fn part_2_should_be_12() {
-        const DATA: &str = "A Y\nB X\nC Z";
-        assert_eq!(12, calculate_result(DATA, Box::new(Part2Parser)));
+        [flag] if flag.starts_with('-') => usage_error(format!("unknown flag `{}`", flag)),
+        // Scores the log with a declarative encoding instead.
+        [path] => {
+            let config = fs::read_to_string(path).unwrap_or_else(|err| {
+                eprintln!("error: cannot read `{}`: {}", path, err);
+                process::exit(1)
+            });
+            let parser = EncodedParser::from_config(&config)
+                .unwrap_or_else(|err| exit_on_error(path, &config, err));
+            println!("{}", score(&parser));
+        }
+        [] => {
+            println!("{}", score(&Part1Parser));
+            println!("{}", score(&Part2Parser));
+        }
+        _ => usage_error(format!("unexpected arguments `{}`", args.join(" "))),
     }
 }
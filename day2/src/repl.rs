@@ -0,0 +1,150 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{battle, score_round, synthesis::Response, tournament::Strategy, HandGesture};
+
+const HELP: &str =
+    "play with rock, paper or scissors (r, p, s); `score` shows the totals, `quit` ends";
+
+/// Rounds played so far between a human and a bot.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Session {
+    bot: Vec<HandGesture>,
+    human: Vec<HandGesture>,
+}
+
+impl Session {
+    pub fn rounds(&self) -> usize {
+        self.human.len()
+    }
+
+    pub fn human_score(&self) -> u32 {
+        self.bot
+            .iter()
+            .zip(&self.human)
+            .map(|(bot, human)| score_round(bot, human))
+            .sum()
+    }
+
+    pub fn bot_score(&self) -> u32 {
+        self.human
+            .iter()
+            .zip(&self.bot)
+            .map(|(human, bot)| score_round(human, bot))
+            .sum()
+    }
+
+    /// The session as an `A X` log with the bot as the enemy, scored by
+    /// [`crate::calculate_result`] with [`crate::Part1Parser`].
+    pub fn to_log(&self) -> String {
+        Response {
+            opponent: self.bot.clone(),
+            moves: self.human.clone(),
+        }
+        .to_guide()
+    }
+
+    fn play(&mut self, bot: HandGesture, human: HandGesture) {
+        self.bot.push(bot);
+        self.human.push(human);
+    }
+}
+
+fn gesture(command: &str) -> Option<HandGesture> {
+    match command {
+        "r" | "rock" => Some(HandGesture::Rock),
+        "p" | "paper" => Some(HandGesture::Paper),
+        "s" | "scissors" => Some(HandGesture::Scissors),
+        _ => None,
+    }
+}
+
+/// Reads moves from `input` until `quit` or end of input, playing each against `bot`.
+pub fn run<R: BufRead, W: Write>(
+    input: R,
+    output: &mut W,
+    bot: &mut dyn Strategy,
+) -> io::Result<Session> {
+    let mut session = Session::default();
+    bot.reset();
+    writeln!(output, "playing against {}; {}", bot.name(), HELP)?;
+
+    for line in input.lines() {
+        let line = line?;
+        let command = line.trim().to_lowercase();
+        match command.as_str() {
+            "" => continue,
+            "quit" | "q" => break,
+            "score" => writeln!(
+                output,
+                "after {} rounds: you {}, {} {}",
+                session.rounds(),
+                session.human_score(),
+                bot.name(),
+                session.bot_score()
+            )?,
+            _ => match gesture(&command) {
+                Some(human) => {
+                    let played = bot.play();
+                    let result = battle(played.clone(), human.clone());
+                    let points = score_round(&played, &human);
+                    bot.observe(&human);
+                    session.play(played.clone(), human);
+                    writeln!(
+                        output,
+                        "{} played {:?}: {:?} (+{}), you {} - {}",
+                        bot.name(),
+                        played,
+                        result,
+                        points,
+                        session.human_score(),
+                        session.bot_score()
+                    )?;
+                }
+                None => writeln!(output, "unknown move `{}`; {}", line.trim(), HELP)?,
+            },
+        }
+    }
+
+    Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        calculate_result,
+        tournament::{Constant, Markov},
+        Part1Parser,
+    };
+
+    #[test]
+    fn session_is_scored_like_a_strategy_guide() {
+        let mut output = Vec::new();
+        let session = run(
+            "paper\nr\n\nbogus\nscore\nScissors\nquit\nrock\n".as_bytes(),
+            &mut output,
+            &mut Constant(HandGesture::Rock),
+        )
+        .unwrap();
+
+        assert_eq!(3, session.rounds());
+        assert_eq!("A Y\nA X\nA Z\n", session.to_log());
+        assert_eq!(8 + 4 + 3, session.human_score());
+        assert_eq!(1 + 4 + 7, session.bot_score());
+        assert_eq!(
+            Ok(session.human_score()),
+            calculate_result(&session.to_log(), Box::new(Part1Parser))
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("unknown move `bogus`"));
+        assert!(output.contains("after 2 rounds: you 12, always Rock 5"));
+    }
+
+    #[test]
+    fn bot_learns_from_the_human() {
+        let moves = "r\n".repeat(20);
+        let session = run(moves.as_bytes(), &mut io::sink(), &mut Markov::default()).unwrap();
+        assert!(session.bot_score() > 2 * session.human_score());
+    }
+}
@@ -0,0 +1,323 @@
+use aoc_core::ParseError;
+
+use crate::{battle, score_round, BattleResult, HandGesture};
+
+/// Your moves against a known opponent column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    pub opponent: Vec<HandGesture>,
+    pub moves: Vec<HandGesture>,
+}
+
+impl Response {
+    pub fn score(&self) -> u32 {
+        self.rounds()
+            .map(|(enemy, you)| score_round(enemy, you))
+            .sum()
+    }
+
+    pub fn wins(&self) -> usize {
+        self.rounds()
+            .filter(|(enemy, you)| battle((*enemy).clone(), (*you).clone()) == BattleResult::Win)
+            .count()
+    }
+
+    /// The guide in the puzzle's `A Y` format, read back with [`crate::Part1Parser`].
+    pub fn to_guide(&self) -> String {
+        self.rounds()
+            .map(|(enemy, you)| {
+                format!(
+                    "{} {}\n",
+                    code(enemy, ["A", "B", "C"]),
+                    code(you, ["X", "Y", "Z"])
+                )
+            })
+            .collect()
+    }
+
+    fn rounds(&self) -> impl Iterator<Item = (&HandGesture, &HandGesture)> {
+        self.opponent.iter().zip(&self.moves)
+    }
+}
+
+fn code(gesture: &HandGesture, letters: [&'static str; 3]) -> &'static str {
+    match gesture {
+        HandGesture::Rock => letters[0],
+        HandGesture::Paper => letters[1],
+        HandGesture::Scissors => letters[2],
+    }
+}
+
+/// Reads a log holding only the opponent column, one `A`, `B` or `C` per line.
+pub fn parse_opponents(log: &str) -> Result<Vec<HandGesture>, ParseError> {
+    log.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            match line {
+                "A" => Ok(HandGesture::Rock),
+                "B" => Ok(HandGesture::Paper),
+                "C" => Ok(HandGesture::Scissors),
+                "" => Err(ParseError::missing(line, "an enemy gesture")),
+                _ => Err(ParseError::unexpected(line, "one of A, B, C").within(line, line)),
+            }
+            .map_err(|err| err.on_line(index + 1))
+        })
+        .collect()
+}
+
+/// The highest scoring reply, which wins every round.
+pub fn maximise(opponent: &[HandGesture]) -> Response {
+    Response {
+        opponent: opponent.to_vec(),
+        moves: opponent
+            .iter()
+            .map(|enemy| BattleResult::Win.when_enemy(enemy.clone()))
+            .collect(),
+    }
+}
+
+/// The highest scoring reply that wins at most `limit` rounds.
+pub fn maximise_with_wins(opponent: &[HandGesture], limit: usize) -> Response {
+    let best_without_win = |enemy: &HandGesture| {
+        [BattleResult::Draw, BattleResult::Lose]
+            .map(|result| result.when_enemy(enemy.clone()))
+            .into_iter()
+            .max_by_key(|you| score_round(enemy, you))
+            .expect("two candidates")
+    };
+    let mut moves: Vec<HandGesture> = opponent.iter().map(best_without_win).collect();
+
+    // Rounds are independent, so spend the wins where they gain the most.
+    let mut gains: Vec<(u32, usize)> = opponent
+        .iter()
+        .zip(&moves)
+        .enumerate()
+        .map(|(index, (enemy, fallback))| {
+            let win = BattleResult::Win.when_enemy(enemy.clone());
+            (
+                score_round(enemy, &win) - score_round(enemy, fallback),
+                index,
+            )
+        })
+        .collect();
+    gains.sort_by_key(|&(gain, index)| (std::cmp::Reverse(gain), index));
+    for &(_, index) in gains.iter().take(limit) {
+        moves[index] = BattleResult::Win.when_enemy(opponent[index].clone());
+    }
+
+    Response {
+        opponent: opponent.to_vec(),
+        moves,
+    }
+}
+
+/// How many rounds against one enemy gesture are won and drawn; the rest are lost.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Outcomes {
+    wins: usize,
+    draws: usize,
+}
+
+/// A reply scoring exactly `target`, or `None` when no sequence of moves does.
+/// Against each enemy gesture, the earliest rounds are won, then drawn.
+///
+/// Losing every round is the cheapest reply; a draw or win adds 1 or 5 points against
+/// Rock, 4 or 8 against Paper and 4 or 5 against Scissors. Only the number of rounds per
+/// enemy gesture matters, so the search takes linear time and memory.
+pub fn exact_score(opponent: &[HandGesture], target: u32) -> Option<Response> {
+    let count = |gesture: HandGesture| opponent.iter().filter(|enemy| **enemy == gesture).count();
+    let (rocks, papers, scissors) = (
+        count(HandGesture::Rock),
+        count(HandGesture::Paper),
+        count(HandGesture::Scissors),
+    );
+    let all_lost: u32 = opponent
+        .iter()
+        .map(|enemy| score_round(enemy, &BattleResult::Lose.when_enemy(enemy.clone())))
+        .sum();
+    let extra = usize::try_from(target.checked_sub(all_lost)?).ok()?;
+
+    let (rock, paper, scissor) = (0..=extra.min(5 * rocks)).find_map(|from_rocks| {
+        let rock = Outcomes {
+            wins: from_rocks / 5,
+            draws: from_rocks % 5,
+        };
+        if rock.wins + rock.draws > rocks {
+            return None;
+        }
+        let (paper, scissor) = split_paper_scissors(extra - from_rocks, papers, scissors)?;
+        Some((rock, paper, scissor))
+    })?;
+
+    let mut remaining = [rock, paper, scissor];
+    let moves = opponent
+        .iter()
+        .map(|enemy| {
+            let outcomes = &mut remaining[match enemy {
+                HandGesture::Rock => 0,
+                HandGesture::Paper => 1,
+                HandGesture::Scissors => 2,
+            }];
+            let result = if outcomes.wins > 0 {
+                outcomes.wins -= 1;
+                BattleResult::Win
+            } else if outcomes.draws > 0 {
+                outcomes.draws -= 1;
+                BattleResult::Draw
+            } else {
+                BattleResult::Lose
+            };
+            result.when_enemy(enemy.clone())
+        })
+        .collect();
+
+    Some(Response {
+        opponent: opponent.to_vec(),
+        moves,
+    })
+}
+
+/// Splits `extra` points over `papers` rounds worth +4/+8 and `scissors` rounds worth +4/+5.
+///
+/// Writing `extra = 4 * k + 5 * j`, `j` counts the scissors wins and must match `extra`
+/// modulo 4; the remaining `k` steps of 4 fit when `k <= 2 * papers + scissors - j`.
+fn split_paper_scissors(
+    extra: usize,
+    papers: usize,
+    scissors: usize,
+) -> Option<(Outcomes, Outcomes)> {
+    let lowest = extra.saturating_sub(8 * papers + 4 * scissors);
+    let highest = scissors.min(extra / 5);
+    let scissor_wins = lowest + (extra % 4 + 4 - lowest % 4) % 4;
+    if scissor_wins > highest {
+        return None;
+    }
+
+    let fours = (extra - 5 * scissor_wins) / 4;
+    let paper_steps = fours.min(2 * papers);
+    let paper_wins = paper_steps.saturating_sub(papers);
+    Some((
+        Outcomes {
+            wins: paper_wins,
+            draws: paper_steps - 2 * paper_wins,
+        },
+        Outcomes {
+            wins: scissor_wins,
+            draws: fours - paper_steps,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_result, Part1Parser};
+
+    fn opponent() -> Vec<HandGesture> {
+        parse_opponents("A\nB\nC").unwrap()
+    }
+
+    #[test]
+    fn maximum_wins_every_round() {
+        let best = maximise(&opponent());
+        assert_eq!(3, best.wins());
+        assert_eq!(24, best.score());
+        assert_eq!("A Y\nB Z\nC X\n", best.to_guide());
+        assert_eq!(
+            Ok(24),
+            calculate_result(&best.to_guide(), Box::new(Part1Parser))
+        );
+    }
+
+    #[test]
+    fn win_limit_spends_wins_where_they_pay_most() {
+        let none = maximise_with_wins(&opponent(), 0);
+        assert_eq!(0, none.wins());
+        assert_eq!(4 + 5 + 6, none.score());
+
+        let one = maximise_with_wins(&opponent(), 1);
+        assert_eq!(1, one.wins());
+        assert_eq!(8 + 5 + 6, one.score());
+
+        assert_eq!(maximise(&opponent()), maximise_with_wins(&opponent(), 10));
+    }
+
+    #[test]
+    fn exact_score_round_trips_through_the_guide() {
+        let opponent: Vec<HandGesture> =
+            crate::parse_battle_log(include_str!("../input.txt"), &Part1Parser)
+                .unwrap()
+                .into_iter()
+                .map(|(enemy, _)| enemy)
+                .collect();
+        for target in [12156, 10835, 2500 * 5 + 1] {
+            let response = exact_score(&opponent, target).unwrap();
+            assert_eq!(target, response.score());
+            assert_eq!(
+                Ok(target),
+                calculate_result(&response.to_guide(), Box::new(Part1Parser))
+            );
+        }
+    }
+
+    #[test]
+    fn unreachable_scores_are_none() {
+        assert_eq!(None, exact_score(&opponent(), 25));
+        assert_eq!(None, exact_score(&opponent(), 0));
+        assert_eq!(
+            Some(3 + 1 + 2),
+            exact_score(&opponent(), 6).map(|r| r.score())
+        );
+        assert_eq!(Some(0), exact_score(&[], 0).map(|r| r.score()));
+    }
+
+    #[test]
+    fn opponent_column_is_validated() {
+        let err = parse_opponents("A\nB Y").unwrap_err();
+        assert_eq!(2, err.position().line);
+        assert_eq!("B Y", err.token());
+    }
+
+    #[test]
+    fn exact_score_agrees_with_brute_force() {
+        use std::collections::HashSet;
+
+        let gestures = [HandGesture::Rock, HandGesture::Paper, HandGesture::Scissors];
+        for rounds in 0..=5u32 {
+            for code in 0..3usize.pow(rounds) {
+                let opponent: Vec<HandGesture> = (0..rounds)
+                    .map(|i| gestures[code / 3usize.pow(i) % 3].clone())
+                    .collect();
+                let reachable: HashSet<u32> = (0..3usize.pow(rounds))
+                    .map(|reply| {
+                        opponent
+                            .iter()
+                            .enumerate()
+                            .map(|(i, enemy)| {
+                                score_round(enemy, &gestures[reply / 3usize.pow(i as u32) % 3])
+                            })
+                            .sum()
+                    })
+                    .collect();
+                for target in 0..=9 * rounds + 1 {
+                    let response = exact_score(&opponent, target);
+                    assert_eq!(reachable.contains(&target), response.is_some());
+                    if let Some(response) = response {
+                        assert_eq!(target, response.score());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exact_score_scales_to_a_million_rounds() {
+        let mut rng = aoc_core::Rng::new(17);
+        let gestures = [HandGesture::Rock, HandGesture::Paper, HandGesture::Scissors];
+        let opponent: Vec<HandGesture> = (0..1_000_000)
+            .map(|_| rng.choose(&gestures).clone())
+            .collect();
+        let response = exact_score(&opponent, 5_000_003).unwrap();
+        assert_eq!(5_000_003, response.score());
+    }
+}
@@ -0,0 +1,369 @@
+use std::{cmp::Reverse, fmt};
+
+use aoc_core::{ParseError, Rng};
+
+use crate::{
+    battle, engine::Gesture, parse_battle_log, BattleResult, HandGesture, LogParser, Scorer,
+};
+
+const GESTURES: [HandGesture; 3] = [HandGesture::Rock, HandGesture::Paper, HandGesture::Scissors];
+
+/// A player that picks a gesture each round and learns from what the opponent played.
+pub trait Strategy {
+    fn name(&self) -> String;
+    fn play(&mut self) -> HandGesture;
+    fn observe(&mut self, opponent: &HandGesture);
+    /// Forgets everything learnt so the next match starts fresh.
+    fn reset(&mut self);
+}
+
+fn index(gesture: &HandGesture) -> usize {
+    Gesture::from(gesture.clone()).0
+}
+
+/// The gesture that beats the predicted one.
+fn counter(predicted: HandGesture) -> HandGesture {
+    BattleResult::Win.when_enemy(predicted)
+}
+
+fn most_frequent(counts: &[u32; 3]) -> Option<HandGesture> {
+    let (best, count) = counts
+        .iter()
+        .enumerate()
+        .max_by_key(|(i, count)| (**count, Reverse(*i)))?;
+    (*count > 0).then(|| GESTURES[best].clone())
+}
+
+pub struct Constant(pub HandGesture);
+
+impl Strategy for Constant {
+    fn name(&self) -> String {
+        format!("always {:?}", self.0)
+    }
+
+    fn play(&mut self) -> HandGesture {
+        self.0.clone()
+    }
+
+    fn observe(&mut self, _: &HandGesture) {}
+
+    fn reset(&mut self) {}
+}
+
+pub struct Uniform {
+    seed: u64,
+    rng: Rng,
+}
+
+impl Uniform {
+    pub fn new(seed: u64) -> Uniform {
+        Uniform {
+            seed,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Strategy for Uniform {
+    fn name(&self) -> String {
+        format!("random #{}", self.seed)
+    }
+
+    fn play(&mut self) -> HandGesture {
+        self.rng.choose(&GESTURES).clone()
+    }
+
+    fn observe(&mut self, _: &HandGesture) {}
+
+    fn reset(&mut self) {
+        self.rng = Rng::new(self.seed);
+    }
+}
+
+/// Counters the opponent's most frequent gesture so far.
+#[derive(Default)]
+pub struct Frequency {
+    counts: [u32; 3],
+}
+
+impl Strategy for Frequency {
+    fn name(&self) -> String {
+        "frequency".to_string()
+    }
+
+    fn play(&mut self) -> HandGesture {
+        counter(most_frequent(&self.counts).unwrap_or(HandGesture::Rock))
+    }
+
+    fn observe(&mut self, opponent: &HandGesture) {
+        self.counts[index(opponent)] += 1;
+    }
+
+    fn reset(&mut self) {
+        self.counts = [0; 3];
+    }
+}
+
+/// Predicts the opponent's next gesture from what usually followed their last one.
+#[derive(Default)]
+pub struct Markov {
+    last: Option<usize>,
+    transitions: [[u32; 3]; 3],
+}
+
+impl Strategy for Markov {
+    fn name(&self) -> String {
+        "markov".to_string()
+    }
+
+    fn play(&mut self) -> HandGesture {
+        let predicted = self
+            .last
+            .and_then(|last| most_frequent(&self.transitions[last]));
+        counter(predicted.unwrap_or(HandGesture::Rock))
+    }
+
+    fn observe(&mut self, opponent: &HandGesture) {
+        let next = index(opponent);
+        if let Some(last) = self.last {
+            self.transitions[last][next] += 1;
+        }
+        self.last = Some(next);
+    }
+
+    fn reset(&mut self) {
+        *self = Markov::default();
+    }
+}
+
+/// Plays your column of a strategy guide, starting over when it runs out.
+pub struct Replay {
+    moves: Vec<HandGesture>,
+    next: usize,
+}
+
+impl Replay {
+    pub fn from_guide(guide: &str, parser: &dyn LogParser) -> Result<Replay, ParseError> {
+        let moves: Vec<HandGesture> = parse_battle_log(guide, parser)?
+            .into_iter()
+            .map(|(_, you)| you)
+            .collect();
+        if moves.is_empty() {
+            return Err(ParseError::missing("", "at least one round").on_line(1));
+        }
+        Ok(Replay { moves, next: 0 })
+    }
+}
+
+impl Strategy for Replay {
+    fn name(&self) -> String {
+        format!("replay ({} rounds)", self.moves.len())
+    }
+
+    fn play(&mut self) -> HandGesture {
+        let gesture = self.moves[self.next].clone();
+        self.next = (self.next + 1) % self.moves.len();
+        gesture
+    }
+
+    fn observe(&mut self, _: &HandGesture) {}
+
+    fn reset(&mut self) {
+        self.next = 0;
+    }
+}
+
+/// Round counts and score of one player, from their own point of view.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    pub score: u64,
+}
+
+impl Record {
+    fn add(&mut self, other: &Record) {
+        self.wins += other.wins;
+        self.draws += other.draws;
+        self.losses += other.losses;
+        self.score += other.score;
+    }
+}
+
+/// Plays `rounds` rounds between fresh copies of `a` and `b`.
+pub fn play_match(a: &mut dyn Strategy, b: &mut dyn Strategy, rounds: u32) -> (Record, Record) {
+    a.reset();
+    b.reset();
+    let (mut record_a, mut record_b) = (Record::default(), Record::default());
+    for _ in 0..rounds {
+        let (move_a, move_b) = (a.play(), b.play());
+        for (record, enemy, you) in [
+            (&mut record_a, &move_b, &move_a),
+            (&mut record_b, &move_a, &move_b),
+        ] {
+            let result = battle(enemy.clone(), you.clone());
+            match result {
+                BattleResult::Win => record.wins += 1,
+                BattleResult::Draw => record.draws += 1,
+                BattleResult::Lose => record.losses += 1,
+            }
+            record.score += u64::from(result.score() + you.score());
+        }
+        a.observe(&move_b);
+        b.observe(&move_a);
+    }
+    (record_a, record_b)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub name: String,
+    pub record: Record,
+}
+
+/// Standings of a round-robin tournament, best total score first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Standings(pub Vec<Standing>);
+
+/// Every entrant plays every other entrant once for `rounds` rounds.
+pub fn round_robin(entrants: &mut [Box<dyn Strategy>], rounds: u32) -> Standings {
+    let mut records = vec![Record::default(); entrants.len()];
+    for i in 0..entrants.len() {
+        for j in i + 1..entrants.len() {
+            let (left, right) = entrants.split_at_mut(j);
+            let (a, b) = play_match(left[i].as_mut(), right[0].as_mut(), rounds);
+            records[i].add(&a);
+            records[j].add(&b);
+        }
+    }
+
+    let mut standings: Vec<Standing> = entrants
+        .iter()
+        .zip(records)
+        .map(|(entrant, record)| Standing {
+            name: entrant.name(),
+            record,
+        })
+        .collect();
+    standings.sort_by_key(|standing| Reverse(standing.record.score));
+    Standings(standings)
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|standing| standing.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("strategy".len());
+        writeln!(
+            f,
+            "{:>2}  {:<width$}  {:>7}  {:>7}  {:>7}  {:>9}",
+            "#", "strategy", "W", "D", "L", "score"
+        )?;
+        for (place, standing) in self.0.iter().enumerate() {
+            let record = &standing.record;
+            writeln!(
+                f,
+                "{:>2}  {:<width$}  {:>7}  {:>7}  {:>7}  {:>9}",
+                place + 1,
+                standing.name,
+                record.wins,
+                record.draws,
+                record.losses,
+                record.score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Part1Parser;
+
+    #[test]
+    fn constant_against_its_counter_always_loses() {
+        let (rock, paper) = play_match(
+            &mut Constant(HandGesture::Rock),
+            &mut Constant(HandGesture::Paper),
+            10,
+        );
+        assert_eq!(
+            Record {
+                wins: 0,
+                draws: 0,
+                losses: 10,
+                score: 10
+            },
+            rock
+        );
+        assert_eq!(
+            Record {
+                wins: 10,
+                draws: 0,
+                losses: 0,
+                score: 80
+            },
+            paper
+        );
+    }
+
+    #[test]
+    fn learners_exploit_a_constant_player() {
+        for mut learner in [
+            Box::new(Frequency::default()) as Box<dyn Strategy>,
+            Box::new(Markov::default()),
+        ] {
+            let (_, record) =
+                play_match(&mut Constant(HandGesture::Scissors), learner.as_mut(), 100);
+            assert!(
+                record.wins >= 98,
+                "{} won only {}",
+                learner.name(),
+                record.wins
+            );
+        }
+    }
+
+    #[test]
+    fn markov_predicts_a_cycle() {
+        let mut cycle = Replay::from_guide("A X\nA Y\nA Z", &Part1Parser).unwrap();
+        let (_, record) = play_match(&mut cycle, &mut Markov::default(), 300);
+        assert!(record.wins >= 295);
+    }
+
+    #[test]
+    fn seeded_random_is_reproducible() {
+        let mut a = Uniform::new(3);
+        let first: Vec<HandGesture> = (0..20).map(|_| a.play()).collect();
+        a.reset();
+        let again: Vec<HandGesture> = (0..20).map(|_| a.play()).collect();
+        assert_eq!(first, again);
+    }
+
+    #[test]
+    fn round_robin_counts_every_round_twice() {
+        let mut entrants: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Constant(HandGesture::Rock)),
+            Box::new(Uniform::new(1)),
+            Box::new(Frequency::default()),
+            Box::new(Markov::default()),
+            Box::new(Replay::from_guide(include_str!("../input.txt"), &Part1Parser).unwrap()),
+        ];
+        let standings = round_robin(&mut entrants, 1000);
+
+        let total = |f: fn(&Record) -> u64| standings.0.iter().map(|s| f(&s.record)).sum::<u64>();
+        assert_eq!(total(|r| r.wins), total(|r| r.losses));
+        assert_eq!(5 * 4 * 1000, total(|r| r.wins + r.draws + r.losses));
+        assert!(standings
+            .0
+            .windows(2)
+            .all(|w| w[0].record.score >= w[1].record.score));
+        assert_eq!(6, standings.to_string().lines().count());
+    }
+}
@@ -0,0 +1,58 @@
+use aoc_core::Rng;
+
+const ITEMS: &[u8; 52] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Rucksack list of `rucksacks` lines rounded up to whole groups of three. Every rucksack
+/// has exactly one item in both compartments and every group exactly one common badge.
+pub fn generate(rng: &mut Rng, rucksacks: usize) -> String {
+    (0..rucksacks.div_ceil(3))
+        .flat_map(|_| group(rng))
+        .map(|rucksack| rucksack + "\n")
+        .collect()
+}
+
+fn group(rng: &mut Rng) -> Vec<String> {
+    let mut items = ITEMS.to_vec();
+    rng.shuffle(&mut items);
+    let badge = items[0];
+
+    // The remaining 51 items are split into disjoint pools, so only the badge is shared.
+    items[1..]
+        .chunks(17)
+        .map(|pool| {
+            let shared = pool[0];
+            let (left_only, right_only) = pool[1..].split_at(8);
+            let len = rng.range(8..=16);
+
+            let mut left = vec![shared, badge];
+            left.extend((2..len).map(|_| *rng.choose(left_only)));
+            let mut right = vec![shared];
+            right.extend((1..len).map(|_| *rng.choose(right_only)));
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+
+            left.into_iter().chain(right).map(char::from).collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_duplicate_in_group, find_duplicates, split_into_compartments};
+
+    #[test]
+    fn one_shared_item_and_one_badge() {
+        let rucksacks = generate(&mut Rng::new(1), 30);
+        let lines: Vec<&str> = rucksacks.lines().collect();
+        assert_eq!(30, lines.len());
+
+        lines.iter().for_each(|line| {
+            let (left, right) = split_into_compartments(line);
+            assert_eq!(1, find_duplicates(left, right).len());
+        });
+        lines
+            .chunks(3)
+            .for_each(|group| assert_eq!(1, find_duplicate_in_group(group).len()));
+    }
+}
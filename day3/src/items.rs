@@ -0,0 +1,153 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    ops::{BitAnd, BitAndAssign},
+};
+
+/// Set of rucksack items as a 52-bit mask, bit `n` standing for the item of priority `n + 1`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+/// Puzzle priority of an item: `a`-`z` are 1-26, `A`-`Z` are 27-52.
+pub fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => char::from(b'a' + priority as u8 - 1),
+        _ => char::from(b'A' + priority as u8 - 27),
+    }
+}
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    /// Items that are not letters have no priority and are left out.
+    pub fn insert(&mut self, item: char) {
+        if let Some(priority) = priority(item) {
+            self.0 |= 1 << (priority - 1);
+        }
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        priority(item).is_some_and(|priority| self.0 >> (priority - 1) & 1 == 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn priorities(&self) -> impl Iterator<Item = u32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let priority = bits.trailing_zeros() + 1;
+            bits &= bits - 1;
+            Some(priority)
+        })
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = char> {
+        self.priorities().map(item)
+    }
+
+    pub fn priority_sum(&self) -> u32 {
+        self.priorities().sum()
+    }
+}
+
+impl From<&str> for ItemSet {
+    fn from(items: &str) -> Self {
+        items.chars().collect()
+    }
+}
+
+impl From<HashSet<char>> for ItemSet {
+    fn from(items: HashSet<char>) -> Self {
+        items.into_iter().collect()
+    }
+}
+
+impl FromIterator<char> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = char>>(items: I) -> Self {
+        let mut set = ItemSet::EMPTY;
+        items.into_iter().for_each(|item| set.insert(item));
+        set
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+}
+
+impl BitAndAssign for ItemSet {
+    fn bitand_assign(&mut self, other: ItemSet) {
+        self.0 &= other.0;
+    }
+}
+
+impl PartialEq<HashSet<char>> for ItemSet {
+    fn eq(&self, other: &HashSet<char>) -> bool {
+        other.len() == self.len() && other.iter().all(|item| self.contains(*item))
+    }
+}
+
+impl PartialEq<ItemSet> for HashSet<char> {
+    fn eq(&self, other: &ItemSet) -> bool {
+        other == self
+    }
+}
+
+impl fmt::Debug for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.items()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priorities_follow_the_puzzle() {
+        assert_eq!(Some(1), priority('a'));
+        assert_eq!(Some(26), priority('z'));
+        assert_eq!(Some(27), priority('A'));
+        assert_eq!(Some(52), priority('Z'));
+        assert_eq!(None, priority('1'));
+        assert!(('a'..='z')
+            .chain('A'..='Z')
+            .all(|c| item(priority(c).unwrap()) == c));
+    }
+
+    #[test]
+    fn intersection_and_bit_iteration() {
+        let set = ItemSet::from("abcZZ") & ItemSet::from("cZx");
+        assert_eq!(2, set.len());
+        assert_eq!(vec!['c', 'Z'], set.items().collect::<Vec<_>>());
+        assert_eq!(3 + 52, set.priority_sum());
+        assert!(!set.contains('a'));
+        assert!((ItemSet::from("ab") & ItemSet::from("cd")).is_empty());
+    }
+
+    #[test]
+    fn non_letters_are_left_out() {
+        assert_eq!(ItemSet::from("a"), ItemSet::from("a1é "));
+        assert_eq!(HashSet::from(['a', 'B']), ItemSet::from("aB"));
+    }
+}
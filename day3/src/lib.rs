@@ -0,0 +1,307 @@
+pub mod generate;
+pub mod items;
+pub mod priority;
+pub mod validate;
+
+use std::collections::HashSet;
+
+use aoc_core::{ParseError, Solution};
+use items::ItemSet;
+use priority::Priority;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    type Input = String;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        check_items(input)?;
+        groups(input, GROUP_SIZE)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part_1_sum(input.clone())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part_2_sum(input.clone())
+    }
+}
+
+pub fn check_items(backpack_items: &str) -> Result<(), ParseError> {
+    backpack_items
+        .lines()
+        .enumerate()
+        .try_for_each(
+            |(index, line)| match line.find(|c: char| !c.is_ascii_alphabetic()) {
+                Some(at) => {
+                    let item = &line[at..at + line[at..].chars().next().unwrap().len_utf8()];
+                    Err(ParseError::unexpected(item, "an item letter a-z or A-Z")
+                        .within(line, item)
+                        .on_line(index + 1))
+                }
+                None => Ok(()),
+            },
+        )
+}
+
+pub fn part_1_sum(backpack_items: String) -> u32 {
+    backpack_items
+        .lines()
+        .map(split_into_compartments)
+        .map(|(left, right)| find_duplicates(left, right))
+        .map(sum)
+        .sum()
+}
+
+/// Sum of the items shared by both compartments, scored by `scheme`.
+pub fn part_1_sum_with<P: Priority + ?Sized>(backpack_items: &str, scheme: &P) -> u64 {
+    backpack_items
+        .lines()
+        .map(split_into_compartments)
+        .map(|(left, right)| sum_with(shared_items(&[left, right]), scheme))
+        .sum()
+}
+
+/// Number of elves sharing a badge in the puzzle.
+pub const GROUP_SIZE: usize = 3;
+
+/// Panics when the last group is incomplete; [`groups`] reports that as an error.
+pub fn part_2_sum(backpack_items: String) -> u32 {
+    badge_sum(&backpack_items, GROUP_SIZE).expect("every group is complete")
+}
+
+pub fn badge_sum(backpack_items: &str, group_size: usize) -> Result<u32, ParseError> {
+    Ok(groups(backpack_items, group_size)?
+        .iter()
+        .map(|group| sum(find_duplicate_in_group(group)))
+        .sum())
+}
+
+/// Sum of the badges of every group of `group_size`, scored by `scheme`.
+pub fn badge_sum_with<P: Priority + ?Sized>(
+    backpack_items: &str,
+    group_size: usize,
+    scheme: &P,
+) -> Result<u64, ParseError> {
+    Ok(groups(backpack_items, group_size)?
+        .iter()
+        .map(|group| sum_with(shared_items(group), scheme))
+        .sum())
+}
+
+/// Splits the list into groups of `size` rucksacks, rejecting an incomplete last group.
+pub fn groups(backpack_items: &str, size: usize) -> Result<Vec<Vec<&str>>, ParseError> {
+    assert!(size > 0, "groups need at least one rucksack");
+    let lines: Vec<&str> = backpack_items.lines().collect();
+    let groups: Vec<Vec<&str>> = lines.chunks(size).map(<[&str]>::to_vec).collect();
+    match groups.last() {
+        Some(last) if last.len() < size => Err(ParseError::missing(
+            lines[lines.len() - 1],
+            format!(
+                "{} more rucksack(s) to complete a group of {}",
+                size - last.len(),
+                size
+            ),
+        )
+        .on_line(lines.len())),
+        _ => Ok(groups),
+    }
+}
+
+/// Splits after half of the items; with an odd count the right compartment gets the extra one.
+pub fn split_into_compartments(input_line: &str) -> (&str, &str) {
+    let half = input_line.chars().count() / 2;
+    let middle = input_line
+        .char_indices()
+        .nth(half)
+        .map_or(input_line.len(), |(at, _)| at);
+    input_line.split_at(middle)
+}
+
+pub fn find_duplicates(left: &str, right: &str) -> ItemSet {
+    ItemSet::from(left) & ItemSet::from(right)
+}
+
+pub fn find_duplicate_in_group(group: &[&str]) -> ItemSet {
+    group
+        .iter()
+        .map(|rucksack| ItemSet::from(*rucksack))
+        .reduce(|common, items| common & items)
+        .unwrap_or_default()
+}
+
+pub fn sum(duplicates: impl Into<ItemSet>) -> u32 {
+    duplicates.into().priority_sum()
+}
+
+/// Items found in every rucksack, for schemes that score items outside the puzzle's letters.
+pub fn shared_items(rucksacks: &[&str]) -> HashSet<char> {
+    rucksacks
+        .iter()
+        .map(|rucksack| rucksack.chars().collect::<HashSet<_>>())
+        .reduce(|common, items| &common & &items)
+        .unwrap_or_default()
+}
+
+/// Items unknown to `scheme` count nothing; summed in `u64` as a scheme may weigh any `u32`.
+pub fn sum_with<P: Priority + ?Sized>(items: HashSet<char>, scheme: &P) -> u64 {
+    items
+        .into_iter()
+        .filter_map(|item| scheme.priority(item))
+        .map(u64::from)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn split_input_line_in_half() {
+        const DATA: &str = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let (left, right) = split_into_compartments(DATA);
+        assert_eq!("vJrwpWtwJgWr", left);
+        assert_eq!("hcsFMMfFFhFp", right);
+    }
+
+    #[test]
+    fn split_respects_multibyte_items() {
+        assert_eq!(("aé", "bc"), split_into_compartments("aébc"));
+        assert_eq!(("a", "bc"), split_into_compartments("abc"));
+        assert_eq!(("", ""), split_into_compartments(""));
+    }
+
+    #[test]
+    fn empty_vector_when_no_duplicates() {
+        let (left, right) = ("a", "b");
+        let duplicates = find_duplicates(left, right);
+        assert!(duplicates.is_empty());
+    }
+
+    #[test]
+    fn list_of_duplicates() {
+        let (left, right) = ("abc", "cbe");
+        let duplicates = find_duplicates(left, right);
+        assert_eq!(HashSet::from(['b', 'c']), duplicates);
+    }
+
+    #[test]
+    fn list_of_duplicates_contains_unique_values() {
+        let (left, right) = ("abcbbbb", "cbebbcc");
+        let duplicates = find_duplicates(left, right);
+        assert_eq!(HashSet::from(['b', 'c']), duplicates);
+    }
+
+    #[test]
+    fn sum_chars() {
+        assert_eq!(1, sum(HashSet::from(['a'])));
+        assert_eq!(26, sum(HashSet::from(['z'])));
+        assert_eq!(27, sum(HashSet::from(['A'])));
+        assert_eq!(52, sum(HashSet::from(['Z'])));
+        assert_eq!(53, sum(HashSet::from(['Z', 'a'])));
+    }
+
+    #[test]
+    fn sum_of_duplicates_part_1() {
+        const DATA: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+        assert_eq!(157, part_1_sum(DATA.to_string()));
+    }
+
+    #[test]
+    fn find_duplicate_in_a_group() {
+        let group1: Box<[&str]> = Box::new([
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+        ]);
+        let group2: Box<[&str]> = Box::new([
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ]);
+
+        assert_eq!(HashSet::from(['r']), find_duplicate_in_group(&group1));
+        assert_eq!(HashSet::from(['Z']), find_duplicate_in_group(&group2));
+    }
+
+    #[test]
+    fn sum_of_duplicates_in_group_part_2() {
+        const DATA: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+        assert_eq!(70, part_2_sum(DATA.to_string()));
+    }
+
+    #[test]
+    fn non_letter_items_are_rejected() {
+        let err = check_items("vJrwpWtw\nab1d").unwrap_err();
+        assert_eq!(2, err.position().line);
+        assert_eq!(3, err.position().column);
+        assert_eq!("1", err.token());
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        const DATA: &str = "ab\nbc\nbd\nxy\nyz\nay";
+        assert_eq!(Ok(2 + 25), badge_sum(DATA, 3));
+        // The middle pair `bd`, `xy` shares nothing.
+        assert_eq!(Ok(2 + 25), badge_sum(DATA, 2));
+        assert_eq!(Ok(0), badge_sum(DATA, 6));
+        assert_eq!(Ok(0), badge_sum("", 3));
+        assert_eq!(Ok(2), badge_sum("ab\nbc\nbd", 3));
+    }
+
+    #[test]
+    fn incomplete_last_group_is_an_error() {
+        let err = badge_sum("ab\nbc\nbd\nxy", 3).unwrap_err();
+        assert_eq!(4, err.position().line);
+        assert_eq!(3, err.position().column);
+        assert!(err.to_string().contains("2 more rucksack(s)"));
+        assert!(Day3::parse("ab\nbc\nbd\nxy").is_err());
+    }
+
+    #[test]
+    fn other_schemes_score_shared_items() {
+        use priority::{Digits, Puzzle, Weights};
+
+        let rucksacks =
+            "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg";
+        assert_eq!(
+            u64::from(part_1_sum(rucksacks.to_string())),
+            part_1_sum_with(rucksacks, &Puzzle)
+        );
+        assert_eq!(
+            badge_sum(rucksacks, 3).map(u64::from),
+            badge_sum_with(rucksacks, 3, &Puzzle)
+        );
+
+        assert_eq!(1 + 3 + 4, part_1_sum_with("1712\n3434", &Digits));
+        assert_eq!(Ok(5), badge_sum_with("15\n59\n56", 3, &Digits));
+
+        let weights = Weights::parse("a 10\nZ 100\n! 1").unwrap();
+        assert_eq!(10 + 100 + 1, part_1_sum_with("aZ!aZ!\nbxxb", &weights));
+
+        let heavy = Weights::parse("a 4294967295\nb 1").unwrap();
+        assert_eq!(4294967296, part_1_sum_with("abab", &heavy));
+        assert_eq!(Ok(2 * 4294967295), badge_sum_with("a\na", 1, &heavy));
+    }
+}
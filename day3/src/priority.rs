@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use aoc_core::{parse_number, ParseError};
+
+use crate::items;
+
+/// How much an item is worth; `None` for items the scheme does not know.
+pub trait Priority {
+    fn priority(&self, item: char) -> Option<u32>;
+}
+
+/// The puzzle's scheme: `a`-`z` are 1-26, `A`-`Z` are 27-52.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle;
+
+impl Priority for Puzzle {
+    fn priority(&self, item: char) -> Option<u32> {
+        items::priority(item)
+    }
+}
+
+/// Digits `0`-`9` are worth their value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Digits;
+
+impl Priority for Digits {
+    fn priority(&self, item: char) -> Option<u32> {
+        item.to_digit(10)
+    }
+}
+
+/// Items ranked by their order in a table, the first one being worth 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet(HashMap<char, u32>);
+
+impl Alphabet {
+    /// Reads the items in order, ignoring whitespace and `#` comment lines.
+    pub fn parse(table: &str) -> Result<Alphabet, ParseError> {
+        let mut ranks = HashMap::new();
+        for (index, line) in table.lines().enumerate() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            for (at, item) in line.char_indices().filter(|(_, c)| !c.is_whitespace()) {
+                let token = &line[at..at + item.len_utf8()];
+                let error = |expected: &str| {
+                    ParseError::unexpected(token, expected)
+                        .within(line, token)
+                        .on_line(index + 1)
+                };
+                if !item.is_ascii() {
+                    return Err(error("an ASCII item"));
+                }
+                if ranks.contains_key(&item) {
+                    return Err(error("each item only once"));
+                }
+                ranks.insert(item, ranks.len() as u32 + 1);
+            }
+        }
+        Ok(Alphabet(ranks))
+    }
+}
+
+impl Priority for Alphabet {
+    fn priority(&self, item: char) -> Option<u32> {
+        self.0.get(&item).copied()
+    }
+}
+
+/// Explicit weight per item.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Weights(pub HashMap<char, u32>);
+
+impl Weights {
+    /// Reads `item weight` lines, skipping blank and `#` comment lines.
+    pub fn parse(table: &str) -> Result<Weights, ParseError> {
+        let mut weights = HashMap::new();
+        for (index, line) in table.lines().enumerate() {
+            let on_line = |err: ParseError| err.on_line(index + 1);
+            let mut tokens = line.split_whitespace();
+            let Some(item) = tokens.next().filter(|token| !token.starts_with('#')) else {
+                continue;
+            };
+            let weight = tokens
+                .next()
+                .ok_or_else(|| on_line(ParseError::missing(line, "a weight")))?;
+            if let Some(extra) = tokens.next() {
+                return Err(on_line(
+                    ParseError::unexpected(extra, "end of line").within(line, extra),
+                ));
+            }
+
+            let mut chars = item.chars();
+            let item = match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii() && !weights.contains_key(&c) => c,
+                _ => {
+                    return Err(on_line(
+                        ParseError::unexpected(item, "a single ASCII item listed once")
+                            .within(line, item),
+                    ))
+                }
+            };
+            let weight = parse_number(weight).map_err(|err| on_line(err.within(line, weight)))?;
+            weights.insert(item, weight);
+        }
+        Ok(Weights(weights))
+    }
+}
+
+impl Priority for Weights {
+    fn priority(&self, item: char) -> Option<u32> {
+        self.0.get(&item).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_priorities() {
+        assert_eq!(Some(1), Puzzle.priority('a'));
+        assert_eq!(Some(26), Puzzle.priority('z'));
+        assert_eq!(Some(27), Puzzle.priority('A'));
+        assert_eq!(Some(52), Puzzle.priority('Z'));
+        assert_eq!(None, Puzzle.priority('1'));
+    }
+
+    #[test]
+    fn digits_score_their_value() {
+        assert_eq!(Some(7), Digits.priority('7'));
+        assert_eq!(None, Digits.priority('a'));
+    }
+
+    #[test]
+    fn alphabet_table_ranks_items_in_order() {
+        let alphabet = Alphabet::parse("# vowels first\naeiou\nb c d").unwrap();
+        assert_eq!(Some(1), alphabet.priority('a'));
+        assert_eq!(Some(6), alphabet.priority('b'));
+        assert_eq!(None, alphabet.priority('z'));
+
+        let err = Alphabet::parse("abc\nda").unwrap_err();
+        assert_eq!((2, 2), (err.position().line, err.position().column));
+        assert_eq!("é", Alphabet::parse("aé").unwrap_err().token());
+    }
+
+    #[test]
+    fn weights_are_read_per_item() {
+        let weights = Weights::parse("a 10\n\n# heavy\nZ 100\n! 1").unwrap();
+        assert_eq!(Some(100), weights.priority('Z'));
+        assert_eq!(Some(1), weights.priority('!'));
+
+        let err = Weights::parse("a 1\nb x").unwrap_err();
+        assert_eq!((2, 3), (err.position().line, err.position().column));
+        assert_eq!("a", Weights::parse("a 1\na 2").unwrap_err().token());
+        assert_eq!(2, Weights::parse("a 1\nb").unwrap_err().position().line);
+    }
+}
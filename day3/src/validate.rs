@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::{find_duplicate_in_group, find_duplicates, split_into_compartments};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Anomaly {
+    /// The items cannot be split into two equal compartments.
+    OddLength(usize),
+    /// An item that is not an ASCII letter, at a 1-based column.
+    InvalidItem {
+        column: usize,
+        item: char,
+    },
+    NoSharedItem,
+    SharedItems(Vec<char>),
+    NoBadge,
+    Badges(Vec<char>),
+    /// The last group has fewer rucksacks than the group size.
+    IncompleteGroup {
+        found: usize,
+        size: usize,
+    },
+}
+
+/// An anomaly and the line it was found on; group anomalies point at the group's first line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub line: usize,
+    pub anomaly: Anomaly,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |items: &[char]| {
+            items
+                .iter()
+                .map(|item| format!("`{}`", item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        write!(f, "line {}: ", self.line)?;
+        match &self.anomaly {
+            Anomaly::OddLength(len) => write!(f, "odd number of items ({})", len),
+            Anomaly::InvalidItem { column, item } => {
+                write!(f, "column {}: `{}` is not an item letter", column, item)
+            }
+            Anomaly::NoSharedItem => write!(f, "compartments share no item"),
+            Anomaly::SharedItems(items) => {
+                write!(f, "compartments share several items: {}", list(items))
+            }
+            Anomaly::NoBadge => write!(f, "group has no badge"),
+            Anomaly::Badges(items) => write!(f, "group has several badges: {}", list(items)),
+            Anomaly::IncompleteGroup { found, size } => {
+                write!(f, "last group has {} of {} rucksacks", found, size)
+            }
+        }
+    }
+}
+
+/// Checks every rucksack and every group of `group_size`, reporting all anomalies in line order.
+pub fn validate(backpack_items: &str, group_size: usize) -> Vec<Finding> {
+    assert!(group_size > 0, "groups need at least one rucksack");
+    let lines: Vec<&str> = backpack_items.lines().collect();
+    let mut findings = Vec::new();
+
+    for (start, group) in lines.chunks(group_size).enumerate() {
+        let first_line = start * group_size + 1;
+        for (offset, rucksack) in group.iter().enumerate() {
+            check_rucksack(first_line + offset, rucksack, &mut findings);
+        }
+
+        let anomaly = if group.len() < group_size {
+            Some(Anomaly::IncompleteGroup {
+                found: group.len(),
+                size: group_size,
+            })
+        } else {
+            let badges: Vec<char> = find_duplicate_in_group(group).items().collect();
+            match badges.len() {
+                0 => Some(Anomaly::NoBadge),
+                1 => None,
+                _ => Some(Anomaly::Badges(badges)),
+            }
+        };
+        if let Some(anomaly) = anomaly {
+            findings.push(Finding {
+                line: first_line,
+                anomaly,
+            });
+        }
+    }
+
+    findings.sort_by_key(|finding| finding.line);
+    findings
+}
+
+fn check_rucksack(line: usize, rucksack: &str, findings: &mut Vec<Finding>) {
+    let mut report = |anomaly| findings.push(Finding { line, anomaly });
+
+    for (index, item) in rucksack.chars().enumerate() {
+        if !item.is_ascii_alphabetic() {
+            report(Anomaly::InvalidItem {
+                column: index + 1,
+                item,
+            });
+        }
+    }
+
+    let len = rucksack.chars().count();
+    if len % 2 == 1 {
+        report(Anomaly::OddLength(len));
+        return;
+    }
+    let (left, right) = split_into_compartments(rucksack);
+    let shared: Vec<char> = find_duplicates(left, right).items().collect();
+    match shared.len() {
+        0 => report(Anomaly::NoSharedItem),
+        1 => {}
+        _ => report(Anomaly::SharedItems(shared)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Rng;
+
+    use super::*;
+    use crate::generate::generate;
+
+    #[test]
+    fn clean_inputs_have_no_findings() {
+        assert_eq!(
+            Vec::<Finding>::new(),
+            validate(include_str!("../input.txt"), 3)
+        );
+        assert_eq!(
+            Vec::<Finding>::new(),
+            validate(&generate(&mut Rng::new(5), 300), 3)
+        );
+    }
+
+    #[test]
+    fn every_anomaly_is_reported_with_its_line() {
+        let findings = validate("abcb\nxyz\naé1a\nabcd\nabab\nbaba", 3);
+        let anomaly = |line, anomaly| Finding { line, anomaly };
+        assert_eq!(
+            vec![
+                anomaly(1, Anomaly::NoBadge),
+                anomaly(2, Anomaly::OddLength(3)),
+                anomaly(
+                    3,
+                    Anomaly::InvalidItem {
+                        column: 2,
+                        item: 'é'
+                    }
+                ),
+                anomaly(
+                    3,
+                    Anomaly::InvalidItem {
+                        column: 3,
+                        item: '1'
+                    }
+                ),
+                anomaly(4, Anomaly::NoSharedItem),
+                anomaly(4, Anomaly::Badges(vec!['a', 'b'])),
+                anomaly(5, Anomaly::SharedItems(vec!['a', 'b'])),
+                anomaly(6, Anomaly::SharedItems(vec!['a', 'b'])),
+            ],
+            findings
+        );
+    }
+
+    #[test]
+    fn incomplete_group_is_reported_at_its_first_line() {
+        let findings = validate("aa\naa\naa\nbb", 3);
+        assert_eq!(
+            vec![Finding {
+                line: 4,
+                anomaly: Anomaly::IncompleteGroup { found: 1, size: 3 }
+            }],
+            findings
+        );
+        assert_eq!(
+            "line 4: last group has 1 of 3 rucksacks",
+            findings[0].to_string()
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use aoc_core::Rng;
+
+/// `pairs` lines of section assignments like `2-4,6-8`.
+pub fn generate(rng: &mut Rng, pairs: usize) -> String {
+    (0..pairs)
+        .map(|_| format!("{},{}\n", assignment(rng), assignment(rng)))
+        .collect()
+}
+
+fn assignment(rng: &mut Rng) -> String {
+    let start = rng.range(1..=99);
+    format!("{}-{}", start, rng.range(start..=99))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pairs;
+
+    #[test]
+    fn generated_pairs_parse() {
+        let pairs = generate(&mut Rng::new(1), 100);
+        assert_eq!(100, parse_pairs(&pairs).unwrap().len());
+    }
+}
@@ -0,0 +1,211 @@
+pub mod generate;
+
+use std::ops::Range;
+
+use aoc_core::{parse_number, ParseError, Solution};
+
+pub struct Day4;
+
+pub type Pair = (Range<i32>, Range<i32>);
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    type Input = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        count_pairs(input, &OverlapPart1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        count_pairs(input, &OverlapPart2)
+    }
+}
+
+pub fn count_overlapses(elves_ranges: &str, ranger: Box<dyn Ranger>) -> Result<usize, ParseError> {
+    Ok(count_pairs(&parse_pairs(elves_ranges)?, ranger.as_ref()))
+}
+
+pub fn parse_pairs(elves_ranges: &str) -> Result<Vec<Pair>, ParseError> {
+    elves_ranges
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_pair(line).map_err(|err| err.on_line(index + 1)))
+        .collect()
+}
+
+pub fn count_pairs(pairs: &[Pair], ranger: &dyn Ranger) -> usize {
+    pairs
+        .iter()
+        .filter(|(range1, range2)| ranger.overlap(range1, range2) || ranger.overlap(range2, range1))
+        .count()
+}
+
+fn parse_pair(line: &str) -> Result<Pair, ParseError> {
+    let (first, second) = line
+        .split_once(',')
+        .ok_or_else(|| ParseError::missing(line, "`,` between two ranges"))?;
+    Ok((
+        range_from(first).map_err(|err| err.within(line, first))?,
+        range_from(second).map_err(|err| err.within(line, second))?,
+    ))
+}
+
+pub fn range_from(elf_range: &str) -> Result<Range<i32>, ParseError> {
+    let (start_id, end_id) = elf_range
+        .split_once('-')
+        .ok_or_else(|| ParseError::missing(elf_range, "`-` between section ids"))?;
+    let start = parse_number::<i32>(start_id).map_err(|err| err.within(elf_range, start_id))?;
+    let end = parse_number::<i32>(end_id).map_err(|err| err.within(elf_range, end_id))?;
+
+    if end < start {
+        return Err(
+            ParseError::unexpected(end_id, "a section id not lower than the range start")
+                .within(elf_range, end_id),
+        );
+    }
+
+    let end = end.checked_add(1).ok_or_else(|| {
+        ParseError::unexpected(end_id, "a section id below the largest `i32`")
+            .within(elf_range, end_id)
+    })?;
+
+    Ok(Range { start, end })
+}
+
+pub trait Ranger {
+    fn overlap(&self, range1: &Range<i32>, range2: &Range<i32>) -> bool;
+}
+
+pub struct OverlapPart1;
+
+impl Ranger for OverlapPart1 {
+    fn overlap(&self, range1: &Range<i32>, range2: &Range<i32>) -> bool {
+        range1.contains(
+            &range2
+                .clone()
+                .next()
+                .expect("expected first element in range2"),
+        ) && range1.contains(
+            &range2
+                .clone()
+                .last()
+                .expect("expected last element in range2"),
+        )
+    }
+}
+
+pub struct OverlapPart2;
+
+impl Ranger for OverlapPart2 {
+    fn overlap(&self, range1: &Range<i32>, range2: &Range<i32>) -> bool {
+        range1.contains(
+            &range2
+                .clone()
+                .next()
+                .expect("expected first element in range2"),
+        ) || range1.contains(
+            &range2
+                .clone()
+                .last()
+                .expect("expected last element in range2"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn true_when_overlap_fully() {
+        let range1 = 1..10;
+        let range2 = 5..7;
+
+        assert!(OverlapPart1.overlap(&range1, &range2));
+        assert!(!OverlapPart1.overlap(&range2, &range1));
+    }
+
+    #[test]
+    fn one_element_range_overlap_fully() {
+        let range1 = 1..2;
+        let range2 = 1..10;
+
+        assert!(!OverlapPart1.overlap(&range1, &range2));
+        assert!(OverlapPart1.overlap(&range2, &range1));
+    }
+
+    #[test]
+    fn should_create_ranges() {
+        const DATA1: &str = "1-1";
+        const DATA2: &str = "1-10";
+        assert_eq!(Ok(1..2), range_from(DATA1));
+        assert_eq!(Ok(1..11), range_from(DATA2));
+    }
+
+    #[test]
+    fn part_1_count_overlapses() {
+        const DATA: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+        assert_eq!(Ok(2), count_overlapses(DATA, Box::new(OverlapPart1)));
+    }
+
+    #[test]
+    fn should_overlap_partialy() {
+        let range1 = 1..10;
+        let range2 = 8..15;
+
+        assert!(OverlapPart2.overlap(&range1, &range2));
+        assert!(OverlapPart2.overlap(&range2, &range1));
+    }
+
+    #[test]
+    fn part_2_count_overlapses() {
+        const DATA: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+        assert_eq!(Ok(4), count_overlapses(DATA, Box::new(OverlapPart2)));
+    }
+
+    #[test]
+    fn invalid_section_id_is_reported_with_position() {
+        const DATA: &str = "2-4,6-8\n2-3,4-x5\n";
+        let err = count_overlapses(DATA, Box::new(OverlapPart1)).unwrap_err();
+        assert_eq!(Position { line: 2, column: 7 }, err.position());
+        assert_eq!("x5", err.token());
+    }
+
+    #[test]
+    fn reversed_range_is_rejected() {
+        let err = range_from("7-3").unwrap_err();
+        assert_eq!(Position { line: 1, column: 3 }, err.position());
+    }
+
+    #[test]
+    fn largest_section_id_is_rejected_instead_of_overflowing() {
+        assert_eq!(Ok(2147483646..2147483647), range_from("2147483646-2147483646"));
+        let err = range_from("1-2147483647").unwrap_err();
+        assert_eq!(Position { line: 1, column: 3 }, err.position());
+        assert_eq!("2147483647", err.token());
+    }
+}
@@ -0,0 +1,69 @@
+use aoc_core::Rng;
+
+const STACKS: usize = 9;
+
+/// Crate drawing of nine stacks followed by `moves` rearrangements. Every move takes
+/// at most as many crates as its source stack holds at that point.
+pub fn generate(rng: &mut Rng, moves: usize) -> String {
+    let mut heights: Vec<usize> = (0..STACKS).map(|_| rng.range(1..=8)).collect();
+    let mut output = drawing(rng, &heights);
+
+    for _ in 0..moves {
+        let loaded: Vec<usize> = (0..STACKS).filter(|stack| heights[*stack] > 0).collect();
+        let from = *rng.choose(&loaded);
+        let to = (from + rng.range(1..=STACKS - 1)) % STACKS;
+        let count = rng.range(1..=heights[from]);
+
+        heights[from] -= count;
+        heights[to] += count;
+        output += &format!("move {} from {} to {}\n", count, from + 1, to + 1);
+    }
+
+    output
+}
+
+fn drawing(rng: &mut Rng, heights: &[usize]) -> String {
+    let tallest = *heights.iter().max().unwrap();
+    let mut drawing: String = (0..tallest)
+        .rev()
+        .map(|level| {
+            heights
+                .iter()
+                .map(|height| {
+                    if *height > level {
+                        format!("[{}]", char::from(b'A' + rng.below(26) as u8))
+                    } else {
+                        "   ".to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+                + "\n"
+        })
+        .collect();
+
+    drawing += &(1..=heights.len())
+        .map(|stack| format!(" {} ", stack))
+        .collect::<Vec<_>>()
+        .join(" ");
+    drawing + "\n\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Solution;
+
+    use super::*;
+    use crate::Day5;
+
+    #[test]
+    fn generated_plan_is_legal() {
+        let plan = generate(&mut Rng::new(1), 200);
+        let input = Day5::parse(&plan).unwrap();
+        assert_eq!(STACKS, input.cargo.len());
+        assert_eq!(200, input.moves.len());
+
+        Day5::part1(&input);
+        Day5::part2(&input);
+    }
+}
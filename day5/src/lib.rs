@@ -0,0 +1,319 @@
+pub mod generate;
+
+use std::cell::RefCell;
+
+use aoc_core::{parse_number, ParseError, Solution};
+
+pub struct Day5;
+
+#[derive(Debug)]
+pub struct CargoPlan {
+    pub cargo: Vec<Vec<char>>,
+    pub moves: Vec<Vec<usize>>,
+}
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const INPUT: &'static str = concat!(
+        include_str!("../input_cargo.txt"),
+        "\n",
+        include_str!("../input_moves.txt")
+    );
+
+    type Input = CargoPlan;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (cargo_schema, cargo_moves) = input.split_once("\n\n").ok_or_else(|| {
+            let last_line = input.lines().last().unwrap_or_default();
+            ParseError::missing(last_line, "an empty line between the drawing and the moves")
+                .on_line(input.lines().count().max(1))
+        })?;
+        let cargo = parse_cargo(cargo_schema);
+        let moves = parse_moves(cargo_moves, cargo.len())
+            .and_then(|moves| check_heights(&cargo, &moves, cargo_moves).map(|_| moves))
+            .map_err(|err| err.on_line(cargo_schema.lines().count() + 2))?;
+
+        Ok(CargoPlan { cargo, moves })
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        rearrange(&input.cargo, &input.moves, &MovablePart1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        rearrange(&input.cargo, &input.moves, &MovablePart2)
+    }
+}
+
+pub fn rearrange_cargo(
+    cargo_schema: &str,
+    cargo_moves: &str,
+    movable: Box<dyn Movable>,
+) -> Result<String, ParseError> {
+    let cargo = parse_cargo(cargo_schema);
+    let moves = parse_moves(cargo_moves, cargo.len())?;
+    check_heights(&cargo, &moves, cargo_moves)?;
+
+    Ok(rearrange(&cargo, &moves, movable.as_ref()))
+}
+
+pub fn rearrange(cargo: &[Vec<char>], moves: &[Vec<usize>], movable: &dyn Movable) -> String {
+    let cargo_stack: &mut Vec<RefCell<Vec<char>>> =
+        &mut cargo.iter().map(|x| RefCell::new(x.clone())).collect();
+
+    moves
+        .iter()
+        .for_each(|cargo_move| movable.move_cargo(cargo_stack, cargo_move.clone()));
+
+    cargo_stack
+        .iter()
+        .filter_map(|v| v.borrow().first().copied())
+        .collect()
+}
+
+pub trait Movable {
+    fn move_cargo(&self, cargo_stack: &mut Vec<RefCell<Vec<char>>>, cargo_move: Vec<usize>);
+}
+
+pub struct MovablePart1;
+impl Movable for MovablePart1 {
+    fn move_cargo(&self, cargo_stack: &mut Vec<RefCell<Vec<char>>>, cargo_move: Vec<usize>) {
+        let count = *cargo_move.first().unwrap();
+        let from = *cargo_move.get(1).unwrap() - 1;
+        let to = *cargo_move.last().unwrap() - 1;
+
+        let mut cargo_from = cargo_stack.get(from).unwrap().borrow_mut();
+        let mut cargo_to = cargo_stack.get(to).unwrap().borrow_mut();
+
+        for _ in 0..count {
+            let cargo = cargo_from.remove(0);
+            cargo_to.insert(0, cargo);
+        }
+    }
+}
+
+pub struct MovablePart2;
+impl Movable for MovablePart2 {
+    fn move_cargo(&self, cargo_stack: &mut Vec<RefCell<Vec<char>>>, cargo_move: Vec<usize>) {
+        let count = *cargo_move.first().unwrap();
+        let from = *cargo_move.get(1).unwrap() - 1;
+        let to = *cargo_move.last().unwrap() - 1;
+
+        let mut cargo_from = cargo_stack.get(from).unwrap().borrow_mut();
+        let mut cargo_to = cargo_stack.get(to).unwrap().borrow_mut();
+
+        cargo_from
+            .drain(0..count)
+            .rev()
+            .for_each(|cargo| cargo_to.insert(0, cargo));
+    }
+}
+
+pub fn parse_moves(cargo_moves: &str, stacks: usize) -> Result<Vec<Vec<usize>>, ParseError> {
+    cargo_moves
+        .lines()
+        .map(|line| {
+            let cargo_move = parse_move(line)?;
+            // Stack numbers are the 4th and 6th token of `move n from a to b`.
+            for (stack, token) in cargo_move[1..]
+                .iter()
+                .zip(line.split(' ').skip(3).step_by(2))
+            {
+                if !(1..=stacks).contains(stack) {
+                    return Err(ParseError::unexpected(
+                        token,
+                        format!("a stack number between 1 and {}", stacks),
+                    )
+                    .within(line, token));
+                }
+            }
+            if cargo_move[1] == cargo_move[2] {
+                let to = line.split(' ').nth(5).expect("parsed move has six tokens");
+                return Err(
+                    ParseError::unexpected(to, "a stack other than the source").within(line, to)
+                );
+            }
+            Ok(cargo_move)
+        })
+        .enumerate()
+        .map(|(index, cargo_move)| cargo_move.map_err(|err| err.on_line(index + 1)))
+        .collect()
+}
+
+/// Replays the stack heights and rejects a move taking more crates than its source holds.
+pub fn check_heights(
+    cargo: &[Vec<char>],
+    moves: &[Vec<usize>],
+    cargo_moves: &str,
+) -> Result<(), ParseError> {
+    let mut heights: Vec<usize> = cargo.iter().map(Vec::len).collect();
+    for (index, (cargo_move, line)) in moves.iter().zip(cargo_moves.lines()).enumerate() {
+        let (count, from, to) = (cargo_move[0], cargo_move[1] - 1, cargo_move[2] - 1);
+        if count > heights[from] {
+            let token = line.split(' ').nth(1).expect("parsed move has six tokens");
+            return Err(ParseError::unexpected(
+                token,
+                format!("at most {} crate(s) on stack {}", heights[from], from + 1),
+            )
+            .within(line, token)
+            .on_line(index + 1));
+        }
+        heights[from] -= count;
+        heights[to] += count;
+    }
+    Ok(())
+}
+
+pub fn parse_move(cargo_move: &str) -> Result<Vec<usize>, ParseError> {
+    let mut tokens = cargo_move.split(' ');
+
+    let numbers = ["move", "from", "to"]
+        .iter()
+        .map(|keyword| {
+            let expected = format!("`{}`", keyword);
+            match tokens.next() {
+                Some(token) if token == *keyword => {}
+                Some(token) => {
+                    return Err(ParseError::unexpected(token, expected).within(cargo_move, token))
+                }
+                None => return Err(ParseError::missing(cargo_move, expected)),
+            }
+            let number = tokens.next().ok_or_else(|| {
+                ParseError::missing(cargo_move, format!("a number after `{}`", keyword))
+            })?;
+            parse_number::<usize>(number).map_err(|err| err.within(cargo_move, number))
+        })
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+
+    match tokens.next() {
+        Some(extra) => Err(ParseError::unexpected(extra, "end of line").within(cargo_move, extra)),
+        None => Ok(numbers),
+    }
+}
+
+pub fn parse_cargo(cargo_schema: &str) -> Vec<Vec<char>> {
+    cargo_schema
+        .lines()
+        // The stack numbers row below the drawing carries no cargo.
+        .filter(|line| line.contains('['))
+        .fold(vec![], |mut acc, x| {
+            x.chars()
+                .skip(1)
+                .step_by(4)
+                .enumerate()
+                .for_each(|(index, cargo_char)| {
+                    let cargo = acc.get_mut(index);
+                    if let Some(v) = cargo {
+                        if cargo_char != ' ' {
+                            v.push(cargo_char);
+                        }
+                    } else if cargo_char != ' ' {
+                        acc.push(vec![cargo_char]);
+                    } else {
+                        acc.push(vec![]);
+                    }
+                });
+            acc
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, vec};
+
+    use aoc_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn parse_cargo_as_vec_of_vecs() {
+        let data1 = fs::read_to_string("input_cargo.txt").unwrap();
+
+        assert_eq!(
+            vec![
+                vec!['P', 'L', 'M', 'N', 'W', 'V', 'B', 'H'],
+                vec!['H', 'Q', 'M'],
+                vec!['L', 'M', 'Q', 'F', 'G', 'B', 'D', 'N'],
+                vec!['G', 'W', 'M', 'Q', 'F', 'T', 'Z'],
+                vec!['P', 'H', 'T', 'M'],
+                vec!['T', 'G', 'H', 'D', 'J', 'M', 'B', 'C'],
+                vec!['R', 'V', 'F', 'B', 'N', 'M'],
+                vec!['S', 'G', 'R', 'M', 'H', 'L', 'P'],
+                vec!['N', 'C', 'B', 'D', 'P'],
+            ],
+            parse_cargo(&data1)
+        );
+    }
+
+    #[test]
+    fn parse_move_as_vec_of_numbers() {
+        const DATA: &str = "move 10 from 14 to 3";
+
+        assert_eq!(Ok(vec![10, 14, 3]), parse_move(DATA));
+    }
+
+    #[test]
+    fn malformed_move_is_reported_with_position() {
+        let err = parse_move("move 10 form 14 to 3").unwrap_err();
+        assert_eq!(Position { line: 1, column: 9 }, err.position());
+        assert_eq!("form", err.token());
+
+        let err = parse_move("move x from 1 to 3").unwrap_err();
+        assert_eq!(Position { line: 1, column: 6 }, err.position());
+    }
+
+    #[test]
+    fn moves_between_unknown_stacks_are_rejected() {
+        let err = parse_moves("move 1 from 1 to 2\nmove 1 from 3 to 1", 2).unwrap_err();
+        assert_eq!(
+            Position {
+                line: 2,
+                column: 13
+            },
+            err.position()
+        );
+        assert_eq!("3", err.token());
+    }
+
+    #[test]
+    fn solves_puzzle_format_with_stack_numbers() {
+        const DATA: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+        let input = Day5::parse(DATA).unwrap();
+        assert_eq!("CMZ", Day5::part1(&input));
+        assert_eq!("MCD", Day5::part2(&input));
+    }
+
+    #[test]
+    fn moves_onto_the_source_stack_are_rejected() {
+        let err = parse_moves("move 1 from 2 to 2", 2).unwrap_err();
+        assert_eq!(
+            Position {
+                line: 1,
+                column: 18
+            },
+            err.position()
+        );
+    }
+
+    #[test]
+    fn moving_more_crates_than_stacked_is_rejected() {
+        let err =
+            Day5::parse("[A]    \n[B] [C]\n 1   2 \n\nmove 1 from 2 to 1\nmove 4 from 1 to 2")
+                .unwrap_err();
+        assert_eq!(Position { line: 6, column: 6 }, err.position());
+        assert_eq!("4", err.token());
+        assert!(Day5::parse("[A]\n 1 \n\nmove 1 from 1 to 1").is_err());
+        assert!(rearrange_cargo("[A]", "move 2 from 1 to 1", Box::new(MovablePart2)).is_err());
+    }
+}
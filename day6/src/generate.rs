@@ -0,0 +1,48 @@
+use aoc_core::Rng;
+
+const MARKER_LEN: usize = 14;
+
+/// Datastream of `len` letters (at least 14) whose first start-of-message marker is planted
+/// at a random position; everything before it uses only three distinct letters.
+pub fn generate(rng: &mut Rng, len: usize) -> String {
+    let len = len.max(MARKER_LEN);
+    let marker_at = rng.range(0..=len - MARKER_LEN);
+
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+    let (prefix_letters, marker_letters) = letters.split_at(3);
+
+    let prefix: Vec<char> = (0..marker_at)
+        .map(|_| *rng.choose(prefix_letters))
+        .collect();
+    // Starting the marker with the last prefix letter keeps windows that straddle the
+    // boundary from being all distinct.
+    let mut marker: Vec<char> = prefix.last().into_iter().copied().collect();
+    marker.extend(marker_letters.iter().take(MARKER_LEN - marker.len()));
+    let suffix: Vec<char> = (marker_at + MARKER_LEN..len)
+        .map(|_| *rng.choose(&letters))
+        .collect();
+
+    prefix.into_iter().chain(marker).chain(suffix).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find_index;
+
+    #[test]
+    fn marker_is_found_where_planted() {
+        let mut rng = Rng::new(1);
+        for len in [14, 15, 100, 1000] {
+            let datastream = generate(&mut rng, len);
+            assert_eq!(len, datastream.len());
+
+            let marker = find_index(datastream.clone(), MARKER_LEN);
+            let mut distinct: Vec<char> = datastream[marker - MARKER_LEN..marker].chars().collect();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(MARKER_LEN, distinct.len());
+        }
+    }
+}
@@ -0,0 +1,99 @@
+pub mod generate;
+
+use std::collections::HashSet;
+
+use aoc_core::{ParseError, Solution};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let datastream = input.trim_end();
+        for len in [4, 14] {
+            if find_marker(datastream, len).is_none() {
+                let last_line = datastream.lines().last().unwrap_or_default();
+                return Err(ParseError::missing(
+                    last_line,
+                    format!("a marker of {} distinct characters", len),
+                )
+                .on_line(datastream.lines().count().max(1)));
+            }
+        }
+        Ok(datastream.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        find_index(input.clone(), 4)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        find_index(input.clone(), 14)
+    }
+}
+
+/// Panics when the stream has no marker; [`find_marker`] reports that as `None`.
+pub fn find_index(datastream: String, len_of_chars: usize) -> usize {
+    find_marker(&datastream, len_of_chars).expect("datastream has a marker")
+}
+
+/// Position just after the first `len_of_chars` distinct characters in a row.
+pub fn find_marker(datastream: &str, len_of_chars: usize) -> Option<usize> {
+    datastream
+        .chars()
+        .enumerate()
+        .find(|(index, _)| {
+            let x: HashSet<char> = datastream.chars().skip(*index).take(len_of_chars).collect();
+            x.len() == len_of_chars
+        })
+        .map(|(index, _)| index + len_of_chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_index_offset_4() {
+        const DATA1: &str = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+        const DATA2: &str = "nppdvjthqldpwncqszvftbrmjlhg";
+        const DATA3: &str = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+        const DATA4: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+
+        assert_eq!(5, find_index(DATA1.to_string(), 4));
+        assert_eq!(6, find_index(DATA2.to_string(), 4));
+        assert_eq!(10, find_index(DATA3.to_string(), 4));
+        assert_eq!(11, find_index(DATA4.to_string(), 4));
+    }
+
+    #[test]
+    fn should_find_index_offset_14() {
+        const DATA1: &str = "bvwbjplbgvbhsrlpgdmjqwftvncz";
+        const DATA2: &str = "nppdvjthqldpwncqszvftbrmjlhg";
+        const DATA3: &str = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+        const DATA4: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+
+        assert_eq!(23, find_index(DATA1.to_string(), 14));
+        assert_eq!(23, find_index(DATA2.to_string(), 14));
+        assert_eq!(29, find_index(DATA3.to_string(), 14));
+        assert_eq!(26, find_index(DATA4.to_string(), 14));
+    }
+
+    #[test]
+    fn stream_without_marker_is_rejected() {
+        assert_eq!(None, find_marker("", 4));
+        assert_eq!(None, find_marker("abcabc", 4));
+
+        let err = Day6::parse("").unwrap_err();
+        assert_eq!(1, err.position().line);
+        let err = Day6::parse("abcdabcd\n").unwrap_err();
+        assert_eq!(9, err.position().column);
+        assert!(err.to_string().contains("14 distinct"));
+    }
+}